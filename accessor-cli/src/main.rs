@@ -0,0 +1,173 @@
+use std::{
+    error::Error,
+    fmt, fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use accessor_rs::{string_interpolator::SpannedStringInterpolator, validation::PathNode};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde_json::Value;
+
+use crate::report::{Diagnostic, Report};
+
+mod report;
+
+/// All templates are fine, warnings don't fail the check.
+const EXIT_OK: u8 = 0;
+/// At least one template has an error.
+const EXIT_TEMPLATE_ERROR: u8 = 1;
+/// The command itself failed, e.g. because a file couldn't be read. Also used by clap for usage
+/// errors.
+const EXIT_FAILURE: u8 = 2;
+
+/// Validates and renders accessor templates.
+#[derive(Debug, Parser)]
+#[command(name = "accessor", version)]
+struct Cli {
+    #[arg(long, value_enum, default_value_t = Format::Text, global = true)]
+    format: Format,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Parses every template and validates it against the schema.
+    Check {
+        /// The JSON Schema document describing the template data.
+        #[arg(long)]
+        schema: PathBuf,
+        #[arg(required = true)]
+        templates: Vec<PathBuf>,
+    },
+    /// Renders the template with the data of a JSON document.
+    Render {
+        #[arg(long)]
+        data: PathBuf,
+        template: PathBuf,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum Format {
+    /// Compiler like diagnostics on stderr.
+    Text,
+    /// A single JSON document on stdout.
+    Json,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let report = match cli.command {
+        Command::Check { schema, templates } => check(&schema, &templates),
+        Command::Render { data, template } => render(&data, &template),
+    };
+    match report {
+        Ok(report) => {
+            report.print(cli.format);
+            match report.has_errors() {
+                true => ExitCode::from(EXIT_TEMPLATE_ERROR),
+                false => ExitCode::from(EXIT_OK),
+            }
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(EXIT_FAILURE)
+        }
+    }
+}
+
+fn check(schema: &Path, templates: &[PathBuf]) -> Result<Report, FileError> {
+    let schema = read_json(schema).and_then(|value| {
+        PathNode::from_json_schema(&value).map_err(|errors| {
+            let errors: Vec<_> = errors.iter().map(ToString::to_string).collect();
+            FileError::new(schema, format!("unsupported schema: {}", errors.join(", ")))
+        })
+    })?;
+
+    let mut report = Report::default();
+    for path in templates {
+        let source = read(path)?;
+        let interpolator = match SpannedStringInterpolator::parse(&source) {
+            Ok(interpolator) => interpolator,
+            Err(err) => {
+                report
+                    .diagnostics
+                    .push(Diagnostic::parser(path, &source, &err));
+                continue;
+            }
+        };
+
+        let validation = schema.validate_interpolator(&interpolator);
+        report.diagnostics.extend(
+            validation
+                .entries()
+                .iter()
+                .map(|entry| Diagnostic::validation(path, &source, entry)),
+        );
+    }
+
+    Ok(report)
+}
+
+fn render(data: &Path, template: &Path) -> Result<Report, FileError> {
+    let data = read_json(data)?;
+    let source = read(template)?;
+
+    let mut report = Report::default();
+    let interpolator = match SpannedStringInterpolator::parse(&source) {
+        Ok(interpolator) => interpolator,
+        Err(err) => {
+            report
+                .diagnostics
+                .push(Diagnostic::parser(template, &source, &err));
+            return Ok(report);
+        }
+    };
+
+    match interpolator.render(&data) {
+        Ok(output) => report.output = Some(output),
+        Err(err) => report
+            .diagnostics
+            .push(Diagnostic::render(template, &source, &err)),
+    }
+    Ok(report)
+}
+
+fn read(path: &Path) -> Result<String, FileError> {
+    fs::read_to_string(path).map_err(|err| FileError::new(path, err))
+}
+
+fn read_json(path: &Path) -> Result<Value, FileError> {
+    serde_json::from_str(&read(path)?).map_err(|err| FileError::new(path, err))
+}
+
+/// A file, that couldn't be used at all.
+#[derive(Debug)]
+struct FileError {
+    path: PathBuf,
+    source: Box<dyn Error>,
+}
+
+impl FileError {
+    fn new(path: &Path, source: impl Into<Box<dyn Error>>) -> Self {
+        FileError {
+            path: path.to_owned(),
+            source: source.into(),
+        }
+    }
+}
+
+impl fmt::Display for FileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.source)
+    }
+}
+
+impl Error for FileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
@@ -0,0 +1,140 @@
+use std::path::Path;
+
+use accessor_rs::{
+    error::{AccessorParserError, AccessorValidationError, RenderError, Severity},
+    AccessorParserSpan,
+};
+use serde::{Serialize, Serializer};
+
+use crate::Format;
+
+/// The result of a command, printed as text or as a single JSON document.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct Report {
+    /// The rendered template.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) output: Option<String>,
+    pub(crate) diagnostics: Vec<Diagnostic>,
+}
+
+impl Report {
+    pub(crate) fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+
+    /// Prints the output to stdout, diagnostics in the text format go to stderr.
+    pub(crate) fn print(&self, format: Format) {
+        match format {
+            Format::Text => {
+                if let Some(output) = &self.output {
+                    print!("{output}");
+                }
+                for diagnostic in &self.diagnostics {
+                    eprintln!("{}", diagnostic.rendered);
+                }
+            }
+            Format::Json => match serde_json::to_string_pretty(self) {
+                Ok(json) => println!("{json}"),
+                Err(err) => eprintln!("error: {err}"),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct Diagnostic {
+    path: String,
+    #[serde(serialize_with = "serialize_severity")]
+    severity: Severity,
+    message: String,
+    help: Option<String>,
+    span: Option<Span>,
+    #[serde(skip)]
+    rendered: String,
+}
+
+impl Diagnostic {
+    pub(crate) fn parser(path: &Path, source: &str, err: &AccessorParserError) -> Self {
+        let kind = err.kind();
+        Diagnostic {
+            path: path.display().to_string(),
+            severity: Severity::Error,
+            message: kind.to_string(),
+            help: kind.help(),
+            span: Some(err.span().into()),
+            rendered: with_path(err.render_diagnostic(source), path),
+        }
+    }
+
+    pub(crate) fn validation(path: &Path, source: &str, err: &AccessorValidationError) -> Self {
+        let kind = err.kind();
+        Diagnostic {
+            path: path.display().to_string(),
+            severity: err.severity(),
+            message: kind.to_string(),
+            help: kind.help(),
+            span: Some(err.span().into()),
+            rendered: with_path(err.render_diagnostic(source), path),
+        }
+    }
+
+    pub(crate) fn render(path: &Path, source: &str, err: &RenderError) -> Self {
+        let rendered = match err.render_diagnostic(source) {
+            Some(rendered) => with_path(rendered, path),
+            None => format!("error: {err}\n --> {}\n", path.display()),
+        };
+        Diagnostic {
+            path: path.display().to_string(),
+            severity: Severity::Error,
+            message: err.to_string(),
+            help: None,
+            span: err.span().map(Into::into),
+            rendered,
+        }
+    }
+}
+
+fn serialize_severity<S: Serializer>(
+    severity: &Severity,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(severity)
+}
+
+/// Prefixes the location of a rendered diagnostic with the path of the template.
+fn with_path(rendered: String, path: &Path) -> String {
+    rendered.replacen("--> ", &format!("--> {}:", path.display()), 1)
+}
+
+#[derive(Debug, Serialize)]
+struct Span {
+    start: Position,
+    end: Position,
+}
+
+/// Lines and columns start at 1, the column is counted in characters.
+#[derive(Debug, Serialize)]
+struct Position {
+    offset: usize,
+    line: u32,
+    column: usize,
+}
+
+impl From<AccessorParserSpan> for Span {
+    fn from(span: AccessorParserSpan) -> Self {
+        Span {
+            start: Position {
+                offset: span.start(),
+                line: span.start_position().line(),
+                column: span.start_position().column(),
+            },
+            end: Position {
+                offset: span.end(),
+                line: span.end_position().line(),
+                column: span.end_position().column(),
+            },
+        }
+    }
+}
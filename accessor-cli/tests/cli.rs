@@ -0,0 +1,121 @@
+use std::process::{Command, Output};
+
+use serde_json::Value;
+
+/// Runs the binary inside the fixtures directory, so paths in the output are relative.
+fn accessor(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_accessor"))
+        .args(args)
+        .current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures"))
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+fn stderr(output: &Output) -> &str {
+    std::str::from_utf8(&output.stderr).unwrap()
+}
+
+#[test]
+fn should_check_templates() {
+    let output = accessor(&[
+        "check",
+        "--schema",
+        "schema.json",
+        "valid.tpl",
+        "deprecated.tpl",
+    ]);
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!(
+        [
+            "warning: field is deprecated",
+            " --> deprecated.tpl:1:16",
+            "  |",
+            "1 | Legacy: ${event.legacy_id}",
+            "  |                ^^^^^^^^^^",
+            "",
+            "",
+        ]
+        .join("\n"),
+        stderr(&output)
+    );
+
+    let output = accessor(&["check", "--schema", "schema.json", "unknown_key.tpl"]);
+    assert_eq!(Some(1), output.status.code());
+    assert!(stderr(&output).starts_with("error: unknown key\n --> unknown_key.tpl:1:19\n"));
+    assert!(stderr(&output).contains("= help: did you mean `creator`?"));
+}
+
+#[test]
+fn should_check_templates_with_json_output() {
+    let output = accessor(&[
+        "check",
+        "--schema",
+        "schema.json",
+        "--format",
+        "json",
+        "valid.tpl",
+        "unclosed.tpl",
+    ]);
+    assert_eq!(Some(1), output.status.code());
+    assert_eq!("", stderr(&output));
+
+    let report: Value = serde_json::from_str(stdout(&output)).unwrap();
+    let diagnostics = report["diagnostics"].as_array().unwrap();
+    assert_eq!(1, diagnostics.len());
+    assert_eq!("unclosed.tpl", diagnostics[0]["path"]);
+    assert_eq!("error", diagnostics[0]["severity"]);
+    assert_eq!("missing closing bracket", diagnostics[0]["message"]);
+    assert_eq!(11, diagnostics[0]["span"]["start"]["offset"]);
+    assert_eq!(12, diagnostics[0]["span"]["start"]["column"]);
+}
+
+#[test]
+fn should_render_template() {
+    let output = accessor(&["render", "--data", "event.json", "valid.tpl"]);
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!("Created by alice at 1700000000000\n", stdout(&output));
+
+    let output = accessor(&[
+        "render",
+        "--data",
+        "event.json",
+        "--format",
+        "json",
+        "unknown_key.tpl",
+    ]);
+    assert_eq!(Some(1), output.status.code());
+
+    let report: Value = serde_json::from_str(stdout(&output)).unwrap();
+    assert_eq!(None, report.get("output"));
+    assert_eq!(
+        "failed to resolve ${event.creater}: missing key for key `creater` at position 1",
+        report["diagnostics"][0]["message"]
+    );
+    let span = &report["diagnostics"][0]["span"];
+    assert_eq!(11, span["start"]["offset"]);
+    assert_eq!(1, span["start"]["line"]);
+    assert_eq!(12, span["start"]["column"]);
+    assert_eq!(27, span["end"]["offset"]);
+
+    let output = accessor(&["render", "--data", "event.json", "unknown_key.tpl"]);
+    assert_eq!(Some(1), output.status.code());
+    assert!(stderr(&output).contains(" --> unknown_key.tpl:1:12\n"));
+    assert!(stderr(&output).contains("1 | Created by ${event.creater}\n"));
+}
+
+#[test]
+fn should_fail_on_unusable_files() {
+    let output = accessor(&["check", "--schema", "missing.json", "valid.tpl"]);
+    assert_eq!(Some(2), output.status.code());
+    assert!(stderr(&output).starts_with("error: missing.json: "));
+
+    let output = accessor(&["render", "--data", "valid.tpl", "valid.tpl"]);
+    assert_eq!(Some(2), output.status.code());
+
+    let output = accessor(&["check", "--schema", "schema.json"]);
+    assert_eq!(Some(2), output.status.code());
+}
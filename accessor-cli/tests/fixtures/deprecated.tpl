@@ -0,0 +1 @@
+Legacy: ${event.legacy_id}
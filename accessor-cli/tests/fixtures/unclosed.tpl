@@ -0,0 +1 @@
+Created by ${event.creator
@@ -0,0 +1 @@
+Created by ${event.creator} at ${event.created_ms}
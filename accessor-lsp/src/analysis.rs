@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use accessor_rs::{
+    completion::complete_at,
+    error::{AccessorValidationError, AccessorValidationErrorKind, Severity},
+    string_interpolator::SpannedStringInterpolator,
+    validation::PathNode,
+    AccessorKey,
+};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CompletionItem, CompletionItemKind,
+    CompletionItemTag, CompletionTextEdit, Diagnostic, DiagnosticSeverity, Hover, HoverContents,
+    MarkupContent, MarkupKind, Range, TextEdit, Url, WorkspaceEdit,
+};
+
+use crate::position::{offset_of, range_of};
+
+/// The number of `possible_keys` of an unknown key offered as quick-fixes.
+const MAX_QUICK_FIXES: usize = 3;
+
+/// Parser errors, and validation problems if the project has a schema.
+pub(crate) fn diagnostics(text: &str, schema: Option<&PathNode>) -> Vec<Diagnostic> {
+    let interpolator = match SpannedStringInterpolator::parse(text) {
+        Ok(interpolator) => interpolator,
+        Err(err) => {
+            let kind = err.kind();
+            return vec![diagnostic(
+                range_of(text, err.span()),
+                Severity::Error,
+                &kind.to_string(),
+                kind.help(),
+            )];
+        }
+    };
+
+    let Some(schema) = schema else {
+        return vec![];
+    };
+    let report = schema.validate_interpolator(&interpolator);
+    report
+        .entries()
+        .iter()
+        .map(|entry| validation_diagnostic(text, entry))
+        .collect()
+}
+
+pub(crate) fn completions(text: &str, offset: usize, schema: &PathNode) -> Vec<CompletionItem> {
+    complete_at(text, offset, schema)
+        .into_iter()
+        .enumerate()
+        .map(|(idx, completion)| {
+            let span = completion.span();
+            let tags = matches!(completion.node(), PathNode::Deprecated { .. })
+                .then(|| vec![CompletionItemTag::DEPRECATED]);
+
+            CompletionItem {
+                label: completion.key().to_owned(),
+                kind: Some(CompletionItemKind::FIELD),
+                detail: Some(describe(completion.node())),
+                tags,
+                // Keep the ranking by edit distance, the client would filter by prefix.
+                sort_text: Some(format!("{idx:04}")),
+                filter_text: Some(text[span.start()..span.end()].to_owned()),
+                text_edit: Some(CompletionTextEdit::Edit(TextEdit {
+                    range: range_of(text, span),
+                    new_text: completion.insert_text().to_owned(),
+                })),
+                ..CompletionItem::default()
+            }
+        })
+        .collect()
+}
+
+/// Describes the node the key under the cursor leads to.
+pub(crate) fn hover(text: &str, offset: usize, schema: &PathNode) -> Option<Hover> {
+    let interpolator = SpannedStringInterpolator::parse(text).ok()?;
+    let accessor = interpolator
+        .segments()
+        .iter()
+        .map(|segment| segment.accessor())
+        .find(|accessor| accessor.span().start() <= offset && offset < accessor.span().end())?;
+
+    let idx = accessor
+        .keys()
+        .iter()
+        .position(|key| offset < key.span().end())?;
+    let keys: Vec<_> = accessor.keys()[..=idx]
+        .iter()
+        .map(|key| key.key().clone())
+        .collect();
+    let node = schema.node_at(&keys)?;
+
+    let key_span = accessor.keys()[idx].span();
+    let path = &text[accessor.span().start()..key_span.end()];
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: format!("`{path}}}`\n\n{}", describe(node)),
+        }),
+        range: Some(range_of(text, key_span)),
+    })
+}
+
+/// Quick-fixes replacing unknown keys inside `range` with their closest known keys.
+pub(crate) fn code_actions(
+    uri: &Url,
+    text: &str,
+    range: Range,
+    schema: &PathNode,
+) -> Vec<CodeActionOrCommand> {
+    let Ok(interpolator) = SpannedStringInterpolator::parse(text) else {
+        return vec![];
+    };
+    let report = schema.validate_interpolator(&interpolator);
+
+    let start = offset_of(text, range.start);
+    let end = offset_of(text, range.end);
+    let mut actions = vec![];
+
+    for entry in report.entries() {
+        let span = entry.span();
+        let AccessorValidationErrorKind::UnknownKey { possible_keys } = entry.kind() else {
+            continue;
+        };
+        if span.end() < start || end < span.start() {
+            continue;
+        }
+
+        let is_root = text[..span.start()].ends_with("${");
+        for (idx, key) in possible_keys.into_iter().take(MAX_QUICK_FIXES).enumerate() {
+            let edit = TextEdit {
+                range: range_of(text, span),
+                new_text: AccessorKey::from(key.clone()).to_template(is_root),
+            };
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: format!("Replace with `{key}`"),
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![validation_diagnostic(text, entry)]),
+                edit: Some(WorkspaceEdit {
+                    changes: Some(HashMap::from([(uri.clone(), vec![edit])])),
+                    ..WorkspaceEdit::default()
+                }),
+                is_preferred: Some(idx == 0),
+                ..CodeAction::default()
+            }));
+        }
+    }
+
+    actions
+}
+
+fn validation_diagnostic(text: &str, entry: &AccessorValidationError) -> Diagnostic {
+    let kind = entry.kind();
+    diagnostic(
+        range_of(text, entry.span()),
+        entry.severity(),
+        &kind.to_string(),
+        kind.help(),
+    )
+}
+
+fn diagnostic(range: Range, severity: Severity, message: &str, help: Option<String>) -> Diagnostic {
+    let severity = match severity {
+        Severity::Error => DiagnosticSeverity::ERROR,
+        Severity::Warning => DiagnosticSeverity::WARNING,
+        Severity::Info => DiagnosticSeverity::INFORMATION,
+    };
+    let message = match help {
+        Some(help) => format!("{message}\nhelp: {help}"),
+        None => message.to_owned(),
+    };
+
+    Diagnostic {
+        range,
+        severity: Some(severity),
+        source: Some("accessor".to_owned()),
+        message,
+        ..Diagnostic::default()
+    }
+}
+
+fn describe(node: &PathNode) -> String {
+    match node {
+        PathNode::Node { children } => format!("node with {} keys", children.len()),
+        PathNode::List {
+            max_len: Some(max_len),
+            ..
+        } => format!("list with at most {max_len} items"),
+        PathNode::List { max_len: None, .. } => "list".to_owned(),
+        PathNode::Map { .. } => "map with arbitrary keys".to_owned(),
+        PathNode::Root => "root, accepts any path".to_owned(),
+        PathNode::ObjectRoot => "object root, accepts any path but can't be rendered".to_owned(),
+        PathNode::KnownField(field_type) => format!("{field_type} field"),
+        PathNode::Deprecated {
+            node,
+            replacement: Some(replacement),
+        } => format!("deprecated {}, use `{replacement}` instead", describe(node)),
+        PathNode::Deprecated {
+            node,
+            replacement: None,
+        } => format!("deprecated {}", describe(node)),
+    }
+}
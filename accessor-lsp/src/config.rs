@@ -0,0 +1,82 @@
+use std::{
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use accessor_rs::{error::JsonSchemaError, validation::PathNode};
+use serde::Deserialize;
+
+/// The project config, searched in the root of the workspace.
+pub(crate) const CONFIG_FILE: &str = "accessor.toml";
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    /// The JSON Schema document describing the template data, relative to the config file.
+    schema: PathBuf,
+}
+
+/// Loads the schema configured in the workspace `root`, `None` if the project has no config.
+pub(crate) fn load_schema(root: &Path) -> Result<Option<PathNode>, ConfigError> {
+    let config_path = root.join(CONFIG_FILE);
+    let config = match fs::read_to_string(&config_path) {
+        Ok(config) => config,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(ConfigError::new(ConfigErrorKind::Io(err), config_path)),
+    };
+    let config: Config = toml::from_str(&config)
+        .map_err(|err| ConfigError::new(ConfigErrorKind::InvalidConfig(err), config_path))?;
+
+    let schema_path = root.join(config.schema);
+    let schema = fs::read_to_string(&schema_path)
+        .map_err(|err| ConfigError::new(ConfigErrorKind::Io(err), schema_path.clone()))?;
+    let schema = serde_json::from_str(&schema)
+        .map_err(|err| ConfigError::new(ConfigErrorKind::InvalidJson(err), schema_path.clone()))?;
+
+    PathNode::from_json_schema(&schema)
+        .map(Some)
+        .map_err(|errors| ConfigError::new(ConfigErrorKind::InvalidSchema(errors), schema_path))
+}
+
+#[derive(Debug)]
+pub(crate) struct ConfigError {
+    kind: ConfigErrorKind,
+    path: PathBuf,
+}
+
+impl ConfigError {
+    fn new(kind: ConfigErrorKind, path: PathBuf) -> Self {
+        ConfigError { kind, path }
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.kind)
+    }
+}
+
+impl Error for ConfigError {}
+
+#[derive(Debug)]
+enum ConfigErrorKind {
+    Io(io::Error),
+    InvalidConfig(toml::de::Error),
+    InvalidJson(serde_json::Error),
+    InvalidSchema(Vec<JsonSchemaError>),
+}
+
+impl fmt::Display for ConfigErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigErrorKind::Io(err) => write!(f, "{err}"),
+            ConfigErrorKind::InvalidConfig(err) => write!(f, "invalid config: {err}"),
+            ConfigErrorKind::InvalidJson(err) => write!(f, "invalid JSON: {err}"),
+            ConfigErrorKind::InvalidSchema(errors) => {
+                let errors: Vec<_> = errors.iter().map(ToString::to_string).collect();
+                write!(f, "unsupported schema: {}", errors.join(", "))
+            }
+        }
+    }
+}
@@ -0,0 +1,210 @@
+//! A language server for accessor templates.
+//!
+//! The schema is read from the `accessor.toml` in the workspace root:
+//!
+//! ```toml
+//! # JSON Schema document describing the template data, relative to the config.
+//! schema = "schema.json"
+//! ```
+
+use std::{collections::HashMap, error::Error, path::PathBuf};
+
+use accessor_rs::validation::PathNode;
+use lsp_server::{Connection, ErrorCode, ExtractError, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+        Notification as LspNotification, PublishDiagnostics, ShowMessage,
+    },
+    request::{CodeActionRequest, Completion, HoverRequest, Request as LspRequest},
+    CodeActionParams, CodeActionProviderCapability, CodeActionResponse, CompletionList,
+    CompletionOptions, CompletionParams, CompletionResponse, Hover, HoverParams,
+    HoverProviderCapability, InitializeParams, MessageType, PublishDiagnosticsParams,
+    ServerCapabilities, ShowMessageParams, TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+};
+
+mod analysis;
+mod config;
+mod position;
+
+/// Serves a single client until it shuts down.
+pub fn run(connection: &Connection) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let capabilities = serde_json::to_value(capabilities())?;
+    let params: InitializeParams = serde_json::from_value(connection.initialize(capabilities)?)?;
+
+    let mut server = Server {
+        connection,
+        documents: HashMap::new(),
+        schema: None,
+    };
+    if let Some(root) = workspace_root(&params) {
+        match config::load_schema(&root) {
+            Ok(schema) => server.schema = schema,
+            Err(err) => server.notify::<ShowMessage>(ShowMessageParams {
+                typ: MessageType::ERROR,
+                message: err.to_string(),
+            })?,
+        }
+    }
+
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+                let response = server.handle_request(request);
+                connection.sender.send(response.into())?;
+            }
+            Message::Notification(notification) => server.handle_notification(notification)?,
+            Message::Response(_) => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec!["{".to_owned(), ".".to_owned()]),
+            ..CompletionOptions::default()
+        }),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        ..ServerCapabilities::default()
+    }
+}
+
+fn workspace_root(params: &InitializeParams) -> Option<PathBuf> {
+    #[allow(deprecated)]
+    let root = match &params.workspace_folders {
+        Some(folders) if !folders.is_empty() => &folders[0].uri,
+        _ => params.root_uri.as_ref()?,
+    };
+    root.to_file_path().ok()
+}
+
+struct Server<'a> {
+    connection: &'a Connection,
+    /// The text of all open documents.
+    documents: HashMap<Url, String>,
+    schema: Option<PathNode>,
+}
+
+impl Server<'_> {
+    fn handle_request(&self, request: Request) -> Response {
+        match request.method.as_str() {
+            Completion::METHOD => respond::<Completion>(request, |params| self.completion(params)),
+            HoverRequest::METHOD => respond::<HoverRequest>(request, |params| self.hover(params)),
+            CodeActionRequest::METHOD => {
+                respond::<CodeActionRequest>(request, |params| self.code_actions(params))
+            }
+            method => Response::new_err(
+                request.id.clone(),
+                ErrorCode::MethodNotFound as i32,
+                format!("unknown method `{method}`"),
+            ),
+        }
+    }
+
+    fn handle_notification(
+        &mut self,
+        notification: Notification,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params = extract::<DidOpenTextDocument>(notification)?;
+                let document = params.text_document;
+                self.update(document.uri, document.text)
+            }
+            DidChangeTextDocument::METHOD => {
+                let params = extract::<DidChangeTextDocument>(notification)?;
+                // The documents are synced in full, so the last change holds the whole text.
+                match params.content_changes.into_iter().last() {
+                    Some(change) => self.update(params.text_document.uri, change.text),
+                    None => Ok(()),
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                let params = extract::<DidCloseTextDocument>(notification)?;
+                let uri = params.text_document.uri;
+                self.documents.remove(&uri);
+                self.notify::<PublishDiagnostics>(PublishDiagnosticsParams {
+                    uri,
+                    diagnostics: vec![],
+                    version: None,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Stores the new text of the document and publishes its diagnostics.
+    fn update(&mut self, uri: Url, text: String) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let diagnostics = analysis::diagnostics(&text, self.schema.as_ref());
+        self.documents.insert(uri.clone(), text);
+        self.notify::<PublishDiagnostics>(PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version: None,
+        })
+    }
+
+    fn completion(&self, params: CompletionParams) -> Option<CompletionResponse> {
+        let position = params.text_document_position;
+        let text = self.documents.get(&position.text_document.uri)?;
+        let schema = self.schema.as_ref()?;
+
+        let offset = position::offset_of(text, position.position);
+        let items = analysis::completions(text, offset, schema);
+        // Candidates are ranked by the typed key, so they have to be requested again while typing.
+        Some(CompletionResponse::List(CompletionList {
+            is_incomplete: true,
+            items,
+        }))
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let position = params.text_document_position_params;
+        let text = self.documents.get(&position.text_document.uri)?;
+        let schema = self.schema.as_ref()?;
+
+        analysis::hover(text, position::offset_of(text, position.position), schema)
+    }
+
+    fn code_actions(&self, params: CodeActionParams) -> Option<CodeActionResponse> {
+        let uri = params.text_document.uri;
+        let text = self.documents.get(&uri)?;
+        let schema = self.schema.as_ref()?;
+
+        Some(analysis::code_actions(&uri, text, params.range, schema))
+    }
+
+    fn notify<N: LspNotification>(
+        &self,
+        params: N::Params,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let notification = Notification::new(N::METHOD.to_owned(), params);
+        self.connection.sender.send(notification.into())?;
+        Ok(())
+    }
+}
+
+fn extract<N: LspNotification>(
+    notification: Notification,
+) -> Result<N::Params, ExtractError<Notification>> {
+    notification.extract(N::METHOD)
+}
+
+fn respond<R: LspRequest>(
+    request: Request,
+    handler: impl FnOnce(R::Params) -> R::Result,
+) -> Response {
+    let id = request.id.clone();
+    match request.extract::<R::Params>(R::METHOD) {
+        Ok((id, params)) => Response::new_ok(id, handler(params)),
+        Err(err) => Response::new_err(id, ErrorCode::InvalidParams as i32, err.to_string()),
+    }
+}
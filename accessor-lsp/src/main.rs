@@ -0,0 +1,13 @@
+use std::error::Error;
+
+use lsp_server::Connection;
+
+fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
+    let (connection, io_threads) = Connection::stdio();
+    accessor_lsp::run(&connection)?;
+
+    // The writer thread only stops, once the connection is dropped.
+    drop(connection);
+    io_threads.join()?;
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use accessor_rs::AccessorParserSpan;
+use lsp_types::{Position, Range};
+
+/// The position of the byte `offset` in `text`. LSP counts characters in UTF-16 code units.
+pub(crate) fn position_of(text: &str, offset: usize) -> Position {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+
+    Position {
+        line: before.matches('\n').count() as u32,
+        character: before[line_start..].encode_utf16().count() as u32,
+    }
+}
+
+pub(crate) fn range_of(text: &str, span: AccessorParserSpan) -> Range {
+    Range {
+        start: position_of(text, span.start()),
+        end: position_of(text, span.end()),
+    }
+}
+
+/// The byte offset of `position` in `text`, positions behind the end of a line are moved to its
+/// end.
+pub(crate) fn offset_of(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(idx) => line_start += idx + 1,
+            None => return text.len(),
+        }
+    }
+    let line_end = text[line_start..]
+        .find('\n')
+        .map_or(text.len(), |idx| line_start + idx);
+
+    let mut character = 0;
+    for (idx, ch) in text[line_start..line_end].char_indices() {
+        if character >= position.character {
+            return line_start + idx;
+        }
+        character += ch.len_utf16() as u32;
+    }
+    line_end
+}
+
+#[cfg(test)]
+mod test {
+    use lsp_types::Position;
+
+    use super::{offset_of, position_of};
+
+    #[test]
+    fn should_convert_between_offsets_and_positions() {
+        let text = "a\u{1F600}b\n${event}\n";
+
+        assert_eq!(Position::new(0, 3), position_of(text, 5));
+        assert_eq!(Position::new(1, 2), position_of(text, 9));
+        assert_eq!(Position::new(2, 0), position_of(text, text.len()));
+
+        assert_eq!(5, offset_of(text, Position::new(0, 3)));
+        assert_eq!(9, offset_of(text, Position::new(1, 2)));
+        assert_eq!(15, offset_of(text, Position::new(1, 42)));
+        assert_eq!(text.len(), offset_of(text, Position::new(7, 0)));
+    }
+}
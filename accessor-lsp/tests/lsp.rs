@@ -0,0 +1,224 @@
+use std::{path::Path, thread};
+
+use lsp_server::{Connection, Message, Notification, Request, RequestId};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidOpenTextDocument, Exit, Initialized,
+        Notification as LspNotification, PublishDiagnostics,
+    },
+    request::{
+        CodeActionRequest, Completion, HoverRequest, Initialize, Request as LspRequest, Shutdown,
+    },
+    CodeActionContext, CodeActionOrCommand, CodeActionParams, CompletionItemTag, CompletionParams,
+    CompletionResponse, CompletionTextEdit, DiagnosticSeverity, DidChangeTextDocumentParams,
+    DidOpenTextDocumentParams, HoverContents, HoverParams, InitializeParams, InitializedParams,
+    Position, PublishDiagnosticsParams, Range, TextDocumentContentChangeEvent,
+    TextDocumentIdentifier, TextDocumentItem, TextDocumentPositionParams, Url,
+    VersionedTextDocumentIdentifier, WorkspaceFolder,
+};
+
+/// A client talking to the server on another thread.
+struct TestClient {
+    connection: Connection,
+    server: thread::JoinHandle<()>,
+    next_id: i32,
+}
+
+impl TestClient {
+    fn start(project: &Path) -> Self {
+        let (server, connection) = Connection::memory();
+        let server = thread::spawn(move || accessor_lsp::run(&server).unwrap());
+        let mut client = TestClient {
+            connection,
+            server,
+            next_id: 0,
+        };
+
+        let uri = Url::from_directory_path(project).unwrap();
+        client.request::<Initialize>(InitializeParams {
+            workspace_folders: Some(vec![WorkspaceFolder {
+                uri,
+                name: "project".to_owned(),
+            }]),
+            ..InitializeParams::default()
+        });
+        client.notify::<Initialized>(InitializedParams {});
+        client
+    }
+
+    fn request<R: LspRequest>(&mut self, params: R::Params) -> R::Result {
+        self.next_id += 1;
+        let id = RequestId::from(self.next_id);
+        let request = Request::new(id.clone(), R::METHOD.to_owned(), params);
+        self.connection.sender.send(request.into()).unwrap();
+
+        loop {
+            match self.connection.receiver.recv().unwrap() {
+                Message::Response(response) if response.id == id => {
+                    return serde_json::from_value(response.result.unwrap()).unwrap();
+                }
+                // Notifications, that were sent before the response, aren't checked.
+                _ => {}
+            }
+        }
+    }
+
+    fn notify<N: LspNotification>(&self, params: N::Params) {
+        let notification = Notification::new(N::METHOD.to_owned(), params);
+        self.connection.sender.send(notification.into()).unwrap();
+    }
+
+    fn receive_diagnostics(&self) -> PublishDiagnosticsParams {
+        match self.connection.receiver.recv().unwrap() {
+            Message::Notification(notification)
+                if notification.method == PublishDiagnostics::METHOD =>
+            {
+                serde_json::from_value(notification.params).unwrap()
+            }
+            message => unreachable!("{:?}", message),
+        }
+    }
+
+    fn open(&self, uri: &Url, text: &str) -> PublishDiagnosticsParams {
+        self.notify::<DidOpenTextDocument>(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem::new(
+                uri.clone(),
+                "accessor".to_owned(),
+                1,
+                text.to_owned(),
+            ),
+        });
+        self.receive_diagnostics()
+    }
+
+    fn shutdown(mut self) {
+        self.request::<Shutdown>(());
+        self.notify::<Exit>(());
+        self.server.join().unwrap();
+    }
+}
+
+fn project() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/project"))
+}
+
+fn position(uri: &Url, line: u32, character: u32) -> TextDocumentPositionParams {
+    TextDocumentPositionParams::new(
+        TextDocumentIdentifier::new(uri.clone()),
+        Position::new(line, character),
+    )
+}
+
+fn range(start: (u32, u32), end: (u32, u32)) -> Range {
+    Range::new(Position::new(start.0, start.1), Position::new(end.0, end.1))
+}
+
+#[test]
+fn should_serve_diagnostics_hover_and_quick_fixes() {
+    let mut client = TestClient::start(project());
+    let uri = Url::from_file_path(project().join("mail.tpl")).unwrap();
+
+    let diagnostics = client.open(
+        &uri,
+        "Created by ${event.creater}\nTag: ${event.tags[0].name} ${item.x}",
+    );
+    assert_eq!(uri, diagnostics.uri);
+    match diagnostics.diagnostics.as_slice() {
+        [diagnostic] => {
+            assert_eq!(range((0, 18), (0, 26)), diagnostic.range);
+            assert_eq!(Some(DiagnosticSeverity::ERROR), diagnostic.severity);
+            assert_eq!(
+                "unknown key\nhelp: did you mean `creator`?",
+                diagnostic.message
+            );
+        }
+        diagnostics => unreachable!("{:?}", diagnostics),
+    }
+
+    let hover = client
+        .request::<HoverRequest>(HoverParams {
+            text_document_position_params: position(&uri, 1, 22),
+            work_done_progress_params: Default::default(),
+        })
+        .unwrap();
+    assert_eq!(Some(range((1, 20), (1, 25))), hover.range);
+    match hover.contents {
+        HoverContents::Markup(content) => {
+            assert_eq!("`${event.tags[0].name}`\n\nstring field", content.value)
+        }
+        contents => unreachable!("{:?}", contents),
+    }
+
+    let actions = client
+        .request::<CodeActionRequest>(CodeActionParams {
+            text_document: TextDocumentIdentifier::new(uri.clone()),
+            range: range((0, 20), (0, 20)),
+            context: CodeActionContext::default(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+        .unwrap();
+    match actions.first() {
+        Some(CodeActionOrCommand::CodeAction(action)) => {
+            assert_eq!("Replace with `creator`", action.title);
+            assert_eq!(Some(true), action.is_preferred);
+
+            let changes = action.edit.as_ref().unwrap().changes.as_ref().unwrap();
+            let edit = &changes[&uri][0];
+            assert_eq!(range((0, 18), (0, 26)), edit.range);
+            assert_eq!(".creator", edit.new_text);
+        }
+        action => unreachable!("{:?}", action),
+    }
+
+    client.shutdown();
+}
+
+#[test]
+fn should_complete_keys_while_typing() {
+    let mut client = TestClient::start(project());
+    let uri = Url::from_file_path(project().join("subject.tpl")).unwrap();
+
+    client.open(&uri, "${event}");
+    client.notify::<DidChangeTextDocument>(DidChangeTextDocumentParams {
+        text_document: VersionedTextDocumentIdentifier::new(uri.clone(), 2),
+        content_changes: vec![TextDocumentContentChangeEvent {
+            range: None,
+            range_length: None,
+            text: "${event.cr".to_owned(),
+        }],
+    });
+    match client.receive_diagnostics().diagnostics.as_slice() {
+        [diagnostic] => assert!(diagnostic.message.starts_with("missing closing bracket")),
+        diagnostics => unreachable!("{:?}", diagnostics),
+    }
+
+    let completions = client.request::<Completion>(CompletionParams {
+        text_document_position: position(&uri, 0, 10),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+        context: None,
+    });
+    let items = match completions {
+        Some(CompletionResponse::List(list)) => list.items,
+        completions => unreachable!("{:?}", completions),
+    };
+
+    assert_eq!(
+        vec!["creator", "created_ms", "tags", "legacy_id"],
+        items
+            .iter()
+            .map(|item| item.label.as_str())
+            .collect::<Vec<_>>()
+    );
+    match &items[0].text_edit {
+        Some(CompletionTextEdit::Edit(edit)) => {
+            assert_eq!(range((0, 8), (0, 10)), edit.range);
+            assert_eq!("creator", edit.new_text);
+        }
+        edit => unreachable!("{:?}", edit),
+    }
+    assert_eq!(Some(vec![CompletionItemTag::DEPRECATED]), items[3].tags);
+
+    client.shutdown();
+}
@@ -0,0 +1,290 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{parse_quote, Data, DeriveInput, Fields, Ident};
+
+use crate::attributes::AccessorAttributes;
+
+struct Methods {
+    get_key: TokenStream,
+    get_index: TokenStream,
+    len: TokenStream,
+    map_keys: TokenStream,
+    as_scalar: TokenStream,
+}
+
+pub(crate) fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param
+            .bounds
+            .push(parse_quote!(::accessor_rs::accessible::Accessible));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let Methods {
+        get_key,
+        get_index,
+        len,
+        map_keys,
+        as_scalar,
+    } = match &input.data {
+        Data::Struct(data) => {
+            let (pattern, methods) = fields_methods(&data.fields, None)?;
+            let Methods {
+                get_key,
+                get_index,
+                len,
+                map_keys,
+                as_scalar,
+            } = methods;
+            let as_scalar = match data.fields {
+                Fields::Unit => {
+                    let name = name.to_string();
+                    quote! {
+                        ::core::option::Option::Some(::accessor_rs::accessible::Scalar::String(
+                            ::std::borrow::Cow::Borrowed(#name),
+                        ))
+                    }
+                }
+                _ => as_scalar,
+            };
+            Methods {
+                get_key: quote! { match self { #pattern => #get_key } },
+                get_index: quote! { match self { #pattern => #get_index } },
+                len: quote! { match self { #pattern => #len } },
+                map_keys: quote! { match self { #pattern => #map_keys } },
+                as_scalar: quote! { match self { #pattern => #as_scalar } },
+            }
+        }
+        Data::Enum(data) => {
+            let mut get_key = vec![];
+            let mut get_index = vec![];
+            let mut len = vec![];
+            let mut map_keys = vec![];
+            let mut as_scalar = vec![];
+            for variant in &data.variants {
+                let attributes = AccessorAttributes::parse(&variant.attrs)?;
+                if attributes.skip {
+                    return Err(syn::Error::new_spanned(
+                        variant,
+                        "variants can't be skipped, use `rename` instead",
+                    ));
+                }
+
+                let variant_name = attributes.key(&variant.ident);
+                let (pattern, methods) = fields_methods(&variant.fields, Some(&variant.ident))?;
+                let Methods {
+                    get_key: variant_get_key,
+                    get_index: variant_get_index,
+                    len: variant_len,
+                    map_keys: variant_map_keys,
+                    as_scalar: variant_as_scalar,
+                } = methods;
+                let variant_as_scalar = match variant.fields {
+                    Fields::Unit => quote! {
+                        ::core::option::Option::Some(::accessor_rs::accessible::Scalar::String(
+                            ::std::borrow::Cow::Borrowed(#variant_name),
+                        ))
+                    },
+                    _ => variant_as_scalar,
+                };
+
+                get_key.push(quote! { #pattern => #variant_get_key, });
+                get_index.push(quote! { #pattern => #variant_get_index, });
+                len.push(quote! { #pattern => #variant_len, });
+                map_keys.push(quote! { #pattern => #variant_map_keys, });
+                as_scalar.push(quote! { #pattern => #variant_as_scalar, });
+            }
+
+            Methods {
+                get_key: quote! { match self { #(#get_key)* } },
+                get_index: quote! { match self { #(#get_index)* } },
+                len: quote! { match self { #(#len)* } },
+                map_keys: quote! { match self { #(#map_keys)* } },
+                as_scalar: quote! { match self { #(#as_scalar)* } },
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "Accessible can't be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        #[automatically_derived]
+        #[allow(unused_variables)]
+        impl #impl_generics ::accessor_rs::accessible::Accessible for #name #ty_generics #where_clause {
+            fn get_key(
+                &self,
+                key: &str,
+            ) -> ::core::result::Result<
+                &dyn ::accessor_rs::accessible::Accessible,
+                ::accessor_rs::error::EvalErrorKind,
+            > {
+                #get_key
+            }
+
+            fn get_index(
+                &self,
+                index: usize,
+            ) -> ::core::result::Result<
+                &dyn ::accessor_rs::accessible::Accessible,
+                ::accessor_rs::error::EvalErrorKind,
+            > {
+                #get_index
+            }
+
+            fn list_len(&self) -> ::core::option::Option<usize> {
+                #len
+            }
+
+            fn map_keys(&self) -> ::core::option::Option<::std::vec::Vec<&str>> {
+                #map_keys
+            }
+
+            fn as_scalar(
+                &self,
+            ) -> ::core::option::Option<::accessor_rs::accessible::Scalar<'_>> {
+                #as_scalar
+            }
+        }
+    })
+}
+
+/// Builds the pattern binding all fields by reference, and the method bodies using these bindings.
+fn fields_methods(fields: &Fields, variant: Option<&Ident>) -> syn::Result<(TokenStream, Methods)> {
+    let path = match variant {
+        Some(variant) => quote! { Self::#variant },
+        None => quote! { Self },
+    };
+
+    match fields {
+        Fields::Named(fields) => {
+            let mut bindings = vec![];
+            let mut arms = vec![];
+            let mut keys = vec![];
+            for (idx, field) in fields.named.iter().enumerate() {
+                let attributes = AccessorAttributes::parse(&field.attrs)?;
+                if attributes.skip {
+                    continue;
+                }
+
+                let ident = field.ident.as_ref().expect("named field");
+                let binding = format_ident!("__field_{}", idx, span = Span::call_site());
+                let key = attributes.key(ident);
+                bindings.push(quote! { #ident: #binding });
+                keys.push(key.clone());
+                arms.push(quote! {
+                    #key => ::core::result::Result::Ok(
+                        #binding as &dyn ::accessor_rs::accessible::Accessible
+                    ),
+                });
+            }
+
+            Ok((
+                quote! { #path { #(#bindings,)* .. } },
+                Methods {
+                    get_key: quote! {
+                        match key {
+                            #(#arms)*
+                            _ => ::core::result::Result::Err(
+                                ::accessor_rs::error::EvalErrorKind::MissingKey,
+                            ),
+                        }
+                    },
+                    get_index: quote! {
+                        ::core::result::Result::Err(
+                            ::accessor_rs::error::EvalErrorKind::NumericIndexInMap,
+                        )
+                    },
+                    len: quote! { ::core::option::Option::None },
+                    map_keys: quote! {
+                        ::core::option::Option::Some(::std::vec![#(#keys),*])
+                    },
+                    as_scalar: quote! { ::core::option::Option::None },
+                },
+            ))
+        }
+        Fields::Unnamed(fields) => {
+            let mut bindings = vec![];
+            for (idx, field) in fields.unnamed.iter().enumerate() {
+                let attributes = AccessorAttributes::parse(&field.attrs)?;
+                if attributes.skip || attributes.rename.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        field,
+                        "accessor attributes are only supported on named fields",
+                    ));
+                }
+                bindings.push(format_ident!("__field_{}", idx, span = Span::call_site()));
+            }
+            let pattern = quote! { #path(#(#bindings),*) };
+
+            // A newtype is transparent and behaves exactly like the wrapped value.
+            if let [binding] = bindings.as_slice() {
+                return Ok((
+                    pattern,
+                    Methods {
+                        get_key: quote! {
+                            ::accessor_rs::accessible::Accessible::get_key(#binding, key)
+                        },
+                        get_index: quote! {
+                            ::accessor_rs::accessible::Accessible::get_index(#binding, index)
+                        },
+                        len: quote! {
+                            ::accessor_rs::accessible::Accessible::list_len(#binding)
+                        },
+                        map_keys: quote! {
+                            ::accessor_rs::accessible::Accessible::map_keys(#binding)
+                        },
+                        as_scalar: quote! {
+                            ::accessor_rs::accessible::Accessible::as_scalar(#binding)
+                        },
+                    },
+                ));
+            }
+
+            let len = bindings.len();
+            let indices = 0..len;
+            Ok((
+                pattern,
+                Methods {
+                    get_key: quote! {
+                        ::core::result::Result::Err(
+                            ::accessor_rs::error::EvalErrorKind::StringKeyInList,
+                        )
+                    },
+                    get_index: quote! {
+                        match index {
+                            #(#indices => ::core::result::Result::Ok(
+                                #bindings as &dyn ::accessor_rs::accessible::Accessible
+                            ),)*
+                            _ => ::core::result::Result::Err(
+                                ::accessor_rs::error::EvalErrorKind::IndexOutOfBounds { len: #len },
+                            ),
+                        }
+                    },
+                    len: quote! { ::core::option::Option::Some(#len) },
+                    map_keys: quote! { ::core::option::Option::None },
+                    as_scalar: quote! { ::core::option::Option::None },
+                },
+            ))
+        }
+        Fields::Unit => Ok((
+            quote! { #path },
+            Methods {
+                get_key: quote! {
+                    ::core::result::Result::Err(::accessor_rs::error::EvalErrorKind::NotIndexable)
+                },
+                get_index: quote! {
+                    ::core::result::Result::Err(::accessor_rs::error::EvalErrorKind::NotIndexable)
+                },
+                len: quote! { ::core::option::Option::None },
+                map_keys: quote! { ::core::option::Option::None },
+                as_scalar: quote! { ::core::option::Option::None },
+            },
+        )),
+    }
+}
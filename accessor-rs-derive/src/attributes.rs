@@ -0,0 +1,44 @@
+use syn::{ext::IdentExt, Attribute, Ident, LitStr};
+
+#[derive(Default)]
+pub(crate) struct AccessorAttributes {
+    pub(crate) rename: Option<String>,
+    pub(crate) skip: bool,
+}
+
+impl AccessorAttributes {
+    pub(crate) fn parse(attributes: &[Attribute]) -> syn::Result<AccessorAttributes> {
+        let mut result = AccessorAttributes::default();
+
+        for attribute in attributes {
+            if !attribute.path().is_ident("accessor") {
+                continue;
+            }
+
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    let rename: LitStr = meta.value()?.parse()?;
+                    result.rename = Some(rename.value());
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("skip") {
+                    result.skip = true;
+                    return Ok(());
+                }
+
+                Err(meta.error("unknown accessor attribute, expected `rename` or `skip`"))
+            })?;
+        }
+
+        Ok(result)
+    }
+
+    /// The key used in templates, for a field or variant called `ident`.
+    pub(crate) fn key(&self, ident: &Ident) -> String {
+        match &self.rename {
+            Some(rename) => rename.clone(),
+            None => ident.unraw().to_string(),
+        }
+    }
+}
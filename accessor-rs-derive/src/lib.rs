@@ -0,0 +1,40 @@
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod accessible;
+mod attributes;
+mod schema;
+
+/// Implements `accessor_rs::accessible::Accessible` for structs and enums.
+///
+/// * Named fields are exposed as keys, unnamed fields as indices.
+/// * Newtypes are transparent and behave like the wrapped value.
+/// * Unit structs and unit variants of enums are rendered as their name.
+///
+/// Fields and variants can be renamed with `#[accessor(rename = "...")]`, fields can be hidden
+/// with `#[accessor(skip)]`.
+#[proc_macro_derive(Accessible, attributes(accessor))]
+pub fn derive_accessible(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    accessible::derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `accessor_rs::validation::HasPathNode` for structs and enums.
+///
+/// * Structs with named fields become a `PathNode::Node` with one child per field.
+/// * Newtypes use the schema of the wrapped type, unit structs are a
+///   `PathNode::KnownField(FieldType::String)`.
+/// * Fieldless enums become a `PathNode::KnownField(FieldType::String)`, all other enums a
+///   `PathNode::Root`.
+///
+/// Supports the same `#[accessor(rename = "...")]` and `#[accessor(skip)]` attributes as
+/// `#[derive(Accessible)]`, so the schema always matches the rendered data.
+#[proc_macro_derive(AccessorSchema, attributes(accessor))]
+pub fn derive_accessor_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    schema::derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
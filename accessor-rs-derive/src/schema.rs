@@ -0,0 +1,117 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Data, DeriveInput, Fields};
+
+use crate::attributes::AccessorAttributes;
+
+pub(crate) fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param
+            .bounds
+            .push(parse_quote!(::accessor_rs::validation::HasPathNode));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let path_node = match &input.data {
+        Data::Struct(data) => fields_path_node(&data.fields)?,
+        Data::Enum(data) => {
+            for variant in &data.variants {
+                let attributes = AccessorAttributes::parse(&variant.attrs)?;
+                if attributes.skip {
+                    return Err(syn::Error::new_spanned(
+                        variant,
+                        "variants can't be skipped, use `rename` instead",
+                    ));
+                }
+            }
+
+            if data
+                .variants
+                .iter()
+                .all(|variant| matches!(variant.fields, Fields::Unit))
+            {
+                // Fieldless enums are rendered as the name of their variant.
+                quote! {
+                    ::accessor_rs::validation::PathNode::KnownField(
+                        ::accessor_rs::validation::FieldType::String,
+                    )
+                }
+            } else {
+                // The variant is only known at runtime, so any path below it has to be accepted.
+                quote! { ::accessor_rs::validation::PathNode::Root }
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "AccessorSchema can't be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::accessor_rs::validation::HasPathNode for #name #ty_generics #where_clause {
+            fn path_node() -> ::accessor_rs::validation::PathNode {
+                #path_node
+            }
+        }
+    })
+}
+
+fn fields_path_node(fields: &Fields) -> syn::Result<TokenStream> {
+    match fields {
+        Fields::Named(fields) => {
+            let mut children = vec![];
+            for field in &fields.named {
+                let attributes = AccessorAttributes::parse(&field.attrs)?;
+                if attributes.skip {
+                    continue;
+                }
+
+                let key = attributes.key(field.ident.as_ref().expect("named field"));
+                let ty = &field.ty;
+                children.push(quote! {
+                    (
+                        ::std::string::String::from(#key),
+                        <#ty as ::accessor_rs::validation::HasPathNode>::path_node(),
+                    )
+                });
+            }
+
+            Ok(quote! {
+                ::accessor_rs::validation::PathNode::Node {
+                    children: ::std::collections::HashMap::from([#(#children),*]),
+                }
+            })
+        }
+        Fields::Unnamed(fields) => {
+            for field in &fields.unnamed {
+                let attributes = AccessorAttributes::parse(&field.attrs)?;
+                if attributes.skip || attributes.rename.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        field,
+                        "accessor attributes are only supported on named fields",
+                    ));
+                }
+            }
+
+            match fields.unnamed.first() {
+                Some(field) if fields.unnamed.len() == 1 => {
+                    let ty = &field.ty;
+                    Ok(quote! { <#ty as ::accessor_rs::validation::HasPathNode>::path_node() })
+                }
+                // The fields can have different types, which a single list item can't describe.
+                _ => Ok(quote! { ::accessor_rs::validation::PathNode::Root }),
+            }
+        }
+        // Unit structs are rendered as their name, like unit variants.
+        Fields::Unit => Ok(quote! {
+            ::accessor_rs::validation::PathNode::KnownField(
+                ::accessor_rs::validation::FieldType::String,
+            )
+        }),
+    }
+}
@@ -0,0 +1,161 @@
+use accessor_rs::{
+    accessible::Accessible,
+    error::{EvalErrorKind, RenderErrorKind},
+    string_interpolator::StringInterpolator,
+    Accessor,
+};
+
+#[derive(Accessible)]
+struct Event {
+    created_ms: u64,
+    #[accessor(rename = "type")]
+    kind: Kind,
+    tags: Vec<Tag>,
+    source: Option<Source>,
+    #[accessor(skip)]
+    #[allow(dead_code)]
+    secret: String,
+    id: EventId,
+}
+
+#[derive(Accessible)]
+struct Tag {
+    name: String,
+}
+
+#[derive(Accessible)]
+struct Source {
+    host: String,
+    port: u16,
+}
+
+#[derive(Accessible)]
+struct EventId(u32);
+
+#[derive(Accessible)]
+enum Kind {
+    #[accessor(rename = "created")]
+    Create,
+    Update {
+        field: String,
+    },
+}
+
+#[derive(Accessible)]
+struct Point(i32, i32);
+
+#[derive(Accessible)]
+struct Unknown;
+
+#[derive(Accessible)]
+struct Wrapper<T> {
+    inner: T,
+}
+
+fn test_event() -> Event {
+    Event {
+        created_ms: 1234,
+        kind: Kind::Create,
+        tags: vec![
+            Tag {
+                name: "first".to_owned(),
+            },
+            Tag {
+                name: "second".to_owned(),
+            },
+        ],
+        source: Some(Source {
+            host: "localhost".to_owned(),
+            port: 8080,
+        }),
+        secret: "hidden".to_owned(),
+        id: EventId(42),
+    }
+}
+
+fn render(template: &str, ctx: &impl Accessible) -> String {
+    let interpolator: StringInterpolator = template.parse().unwrap();
+    interpolator.render(ctx).unwrap()
+}
+
+#[test]
+fn should_render_struct_fields() {
+    let event = Wrapper {
+        inner: test_event(),
+    };
+
+    assert_eq!(
+        "1234 first second localhost:8080 42",
+        render(
+            "${inner.created_ms} ${inner.tags[0].name} ${inner.tags[1].name} \
+             ${inner.source.host}:${inner.source.port} ${inner.id}",
+            &event
+        )
+    );
+    assert_eq!("second", render("${inner.tags[-1].name}", &event));
+    assert_eq!("first, second", render("${inner.tags[*].name}", &event));
+    assert_eq!("localhost, 8080", render("${inner.source.*}", &event));
+}
+
+#[test]
+fn should_render_enum_variants() {
+    let mut event = Wrapper {
+        inner: test_event(),
+    };
+    assert_eq!("created", render("${inner.type}", &event));
+
+    event.inner.kind = Kind::Update {
+        field: "name".to_owned(),
+    };
+    assert_eq!("name", render("${inner.type.field}", &event));
+
+    let interpolator: StringInterpolator = "${inner.type}".parse().unwrap();
+    let err = interpolator.render(&event).unwrap_err();
+    assert_eq!(&RenderErrorKind::NotStringRepresentable, err.kind());
+}
+
+#[test]
+fn should_render_unit_structs() {
+    let unknown = Wrapper { inner: Unknown };
+    assert_eq!("Unknown", render("${inner}", &unknown));
+}
+
+#[test]
+fn should_render_tuple_structs() {
+    let point = Wrapper {
+        inner: Point(3, -4),
+    };
+    assert_eq!("(3, -4)", render("(${inner[0]}, ${inner[1]})", &point));
+    assert_eq!("-4", render("${inner[-1]}", &point));
+
+    let accessor: Accessor = "${inner[2]}".parse().unwrap();
+    let err = accessor.resolve(&point).err().unwrap();
+    assert_eq!(EvalErrorKind::IndexOutOfBounds { len: 2 }, err.kind());
+}
+
+#[test]
+fn should_render_missing_option_as_empty() {
+    let mut event = Wrapper {
+        inner: test_event(),
+    };
+    event.inner.source = None;
+
+    let accessor: Accessor = "${inner.source.host}".parse().unwrap();
+    let err = accessor.resolve(&event).err().unwrap();
+    assert_eq!(EvalErrorKind::NotIndexable, err.kind());
+    assert_eq!("", render("${inner.source}", &event));
+}
+
+#[test]
+fn should_not_access_skipped_or_renamed_fields() {
+    let event = Wrapper {
+        inner: test_event(),
+    };
+
+    for template in ["${inner.secret}", "${inner.kind}"] {
+        let accessor: Accessor = template.parse().unwrap();
+        let err = accessor.resolve(&event).err().unwrap();
+        assert_eq!(EvalErrorKind::MissingKey, err.kind());
+        assert_eq!(1, err.position());
+    }
+}
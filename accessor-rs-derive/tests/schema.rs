@@ -0,0 +1,118 @@
+// The types below only describe a schema and are never constructed.
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use accessor_rs::{
+    error::AccessorValidationErrorKind,
+    validation::{AccessorSchema, FieldType, HasPathNode, PathNode},
+    SpannedAccessor,
+};
+
+#[derive(AccessorSchema)]
+struct Context {
+    event: Event,
+    item: Option<String>,
+}
+
+#[derive(AccessorSchema)]
+struct Event {
+    created_ms: u64,
+    #[accessor(rename = "type")]
+    kind: Kind,
+    payload: HashMap<String, String>,
+    source: Option<Source>,
+    #[accessor(skip)]
+    secret: String,
+    id: EventId,
+}
+
+#[derive(AccessorSchema)]
+struct Source {
+    host: String,
+}
+
+#[derive(AccessorSchema)]
+struct EventId(u32);
+
+#[derive(AccessorSchema)]
+enum Kind {
+    Create,
+    Update,
+}
+
+#[derive(AccessorSchema)]
+struct Unknown;
+
+#[derive(AccessorSchema)]
+struct Wrapper<T> {
+    inner: T,
+}
+
+fn validate(template: &str) -> Result<(), AccessorValidationErrorKind> {
+    let accessor = SpannedAccessor::parse(template).unwrap();
+    match Context::path_node()
+        .validate_accessor(&accessor)
+        .errors()
+        .next()
+    {
+        Some(err) => Err(err.kind()),
+        None => Ok(()),
+    }
+}
+
+#[test]
+fn should_derive_path_node() {
+    let expected = PathNode::Node {
+        children: HashMap::from([(
+            "inner".to_owned(),
+            PathNode::Node {
+                children: HashMap::from([(
+                    "host".to_owned(),
+                    PathNode::KnownField(FieldType::String),
+                )]),
+            },
+        )]),
+    };
+    assert_eq!(expected, Wrapper::<Source>::path_node());
+    assert_eq!(
+        PathNode::KnownField(FieldType::Integer),
+        EventId::path_node()
+    );
+    assert_eq!(PathNode::KnownField(FieldType::String), Kind::path_node());
+    assert_eq!(
+        PathNode::KnownField(FieldType::String),
+        Unknown::path_node()
+    );
+}
+
+#[test]
+fn should_validate_against_derived_path_node() {
+    validate("${event.created_ms}").unwrap();
+    validate("${event.type}").unwrap();
+    validate("${event.payload.anything}").unwrap();
+    validate("${event.source.host}").unwrap();
+    validate("${event.id}").unwrap();
+    validate("${item}").unwrap();
+}
+
+#[test]
+fn should_reject_skipped_and_unknown_fields() {
+    match validate("${event.secret}") {
+        Err(AccessorValidationErrorKind::UnknownKey { possible_keys }) => {
+            assert!(!possible_keys.contains(&"secret".to_owned()));
+            assert!(possible_keys.contains(&"type".to_owned()));
+        }
+        err => unreachable!("{:?}", err),
+    }
+
+    match validate("${event.created_ms.value}") {
+        Err(AccessorValidationErrorKind::NotIndexable) => {}
+        err => unreachable!("{:?}", err),
+    }
+
+    match validate("${event.payload.anything.value}") {
+        Err(AccessorValidationErrorKind::NotIndexable) => {}
+        err => unreachable!("{:?}", err),
+    }
+}
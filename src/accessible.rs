@@ -0,0 +1,713 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    fmt,
+    hash::BuildHasher,
+};
+
+use crate::{
+    error::{EvalError, EvalErrorKind},
+    Accessor, AccessorKey, SpannedAccessor,
+};
+
+#[cfg(feature = "derive")]
+pub use accessor_rs_derive::Accessible;
+
+/// A data structure, that can be walked by an [`Accessor`].
+///
+/// Maps should implement [`Accessible::get_key`] and [`Accessible::map_keys`], without which `.*`
+/// can't be resolved. Lists should implement [`Accessible::get_index`] and
+/// [`Accessible::list_len`], without which negative indices like `[-1]`, slices like `[1:3]` and
+/// `[*]` can't be resolved. Scalars can rely on the default implementations, which refuse any
+/// further indexing, and only have to provide [`Accessible::as_scalar`].
+pub trait Accessible {
+    fn get_key(&self, _key: &str) -> Result<&dyn Accessible, EvalErrorKind> {
+        Err(EvalErrorKind::NotIndexable)
+    }
+
+    fn get_index(&self, _index: usize) -> Result<&dyn Accessible, EvalErrorKind> {
+        Err(EvalErrorKind::NotIndexable)
+    }
+
+    /// The number of items of a list, used to resolve indices counting from the end.
+    fn list_len(&self) -> Option<usize> {
+        None
+    }
+
+    /// All keys of a map, used to resolve `.*`. Maps without an order of their own should sort
+    /// the keys, so templates render the same every time.
+    fn map_keys(&self) -> Option<Vec<&str>> {
+        None
+    }
+
+    /// Returns `None` for values, that can't be represented as a string, like maps and lists.
+    fn as_scalar(&self) -> Option<Scalar<'_>> {
+        None
+    }
+}
+
+/// A value, that can be rendered into a string template.
+///
+/// The string representation is:
+/// * `Null`: the empty string
+/// * `Bool`: `true` or `false`
+/// * `Integer`: the decimal representation, e.g. `-42`
+/// * `Float`: the shortest representation that parses back to the same value, e.g. `1.5` or `3`
+/// * `String`: the string itself, without quotes or escaping
+#[derive(Debug, Clone, PartialEq)]
+pub enum Scalar<'a> {
+    Null,
+    Bool(bool),
+    Integer(i128),
+    Float(f64),
+    String(Cow<'a, str>),
+}
+
+impl fmt::Display for Scalar<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scalar::Null => Ok(()),
+            Scalar::Bool(value) => write!(f, "{value}"),
+            Scalar::Integer(value) => write!(f, "{value}"),
+            Scalar::Float(value) => write!(f, "{value}"),
+            Scalar::String(value) => f.write_str(value),
+        }
+    }
+}
+
+impl Accessor {
+    pub fn resolve<'value>(
+        &self,
+        value: &'value dyn Accessible,
+    ) -> Result<&'value dyn Accessible, EvalError> {
+        resolve_keys(value, self.keys.iter())
+    }
+
+    /// Resolves all values matched by a [multi-valued](Accessor::is_multi_valued) accessor, in
+    /// the order of the lists and maps they are taken from. Other accessors resolve to a single
+    /// value.
+    pub fn resolve_all<'value>(
+        &self,
+        value: &'value dyn Accessible,
+    ) -> Result<Vec<&'value dyn Accessible>, EvalError> {
+        Ok(resolve_all_keys(value, self.keys.iter())?
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect())
+    }
+
+    /// Like [`Accessor::resolve_all`], but every value comes with the concrete path leading to
+    /// it, e.g. `${tags[1].name}` for `${tags[*].name}`.
+    pub fn resolve_paths<'value>(
+        &self,
+        value: &'value dyn Accessible,
+    ) -> Result<Vec<(Accessor, &'value dyn Accessible)>, EvalError> {
+        Ok(resolve_all_keys(value, self.keys.iter())?
+            .into_iter()
+            .map(|(keys, value)| (Accessor { keys: keys.into() }, value))
+            .collect())
+    }
+}
+
+impl SpannedAccessor {
+    pub fn resolve<'value>(
+        &self,
+        value: &'value dyn Accessible,
+    ) -> Result<&'value dyn Accessible, EvalError> {
+        resolve_keys(value, self.keys.iter().map(|key| &key.key))
+    }
+
+    /// See [`Accessor::resolve_all`].
+    pub fn resolve_all<'value>(
+        &self,
+        value: &'value dyn Accessible,
+    ) -> Result<Vec<&'value dyn Accessible>, EvalError> {
+        let keys = self.keys.iter().map(|key| &key.key);
+        Ok(resolve_all_keys(value, keys)?
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect())
+    }
+
+    /// See [`Accessor::resolve_paths`].
+    pub fn resolve_paths<'value>(
+        &self,
+        value: &'value dyn Accessible,
+    ) -> Result<Vec<(Accessor, &'value dyn Accessible)>, EvalError> {
+        let keys = self.keys.iter().map(|key| &key.key);
+        Ok(resolve_all_keys(value, keys)?
+            .into_iter()
+            .map(|(keys, value)| (Accessor { keys: keys.into() }, value))
+            .collect())
+    }
+}
+
+fn resolve_keys<'value, 'key>(
+    value: &'value dyn Accessible,
+    keys: impl Iterator<Item = &'key AccessorKey>,
+) -> Result<&'value dyn Accessible, EvalError> {
+    let mut value = value;
+    for (position, key) in keys.enumerate() {
+        value = resolve_key(value, key).map_err(|kind| EvalError {
+            kind,
+            key: key.clone(),
+            position,
+        })?;
+    }
+
+    Ok(value)
+}
+
+/// A resolved value with the concrete keys leading to it.
+type Match<'value> = (Vec<AccessorKey>, &'value dyn Accessible);
+
+/// Resolves the keys for every value matched so far, and keeps track of the concrete keys.
+fn resolve_all_keys<'value, 'key>(
+    value: &'value dyn Accessible,
+    keys: impl Iterator<Item = &'key AccessorKey>,
+) -> Result<Vec<Match<'value>>, EvalError> {
+    let mut matches = vec![(vec![], value)];
+    for (position, key) in keys.enumerate() {
+        let mut next = Vec::with_capacity(matches.len());
+        for (path, value) in matches {
+            let resolved = concrete_keys(value, key).and_then(|concrete_keys| {
+                concrete_keys
+                    .into_iter()
+                    .map(|concrete_key| {
+                        let value = resolve_key(value, &concrete_key)?;
+                        let mut path = path.clone();
+                        path.push(concrete_key);
+                        Ok((path, value))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            });
+
+            next.extend(resolved.map_err(|kind| EvalError {
+                kind,
+                key: key.clone(),
+                position,
+            })?);
+        }
+        matches = next;
+    }
+
+    Ok(matches)
+}
+
+/// The single-valued keys a key stands for in `value`.
+fn concrete_keys(
+    value: &dyn Accessible,
+    key: &AccessorKey,
+) -> Result<Vec<AccessorKey>, EvalErrorKind> {
+    match key {
+        AccessorKey::Slice(slice) => match value.list_len() {
+            Some(len) => Ok(slice.indices(len).map(AccessorKey::Numeric).collect()),
+            None => Err(not_a_list(value)),
+        },
+        AccessorKey::IndexWildcard => match value.list_len() {
+            Some(len) => Ok((0..len).map(AccessorKey::Numeric).collect()),
+            None => Err(not_a_list(value)),
+        },
+        AccessorKey::KeyWildcard => match value.map_keys() {
+            Some(keys) => Ok(keys
+                .into_iter()
+                .map(|key| AccessorKey::String(key.into()))
+                .collect()),
+            None => Err(not_a_map(value)),
+        },
+        key => Ok(vec![key.clone()]),
+    }
+}
+
+fn resolve_key<'value>(
+    value: &'value dyn Accessible,
+    key: &AccessorKey,
+) -> Result<&'value dyn Accessible, EvalErrorKind> {
+    match key {
+        AccessorKey::String(key) => value.get_key(key),
+        AccessorKey::Numeric(index) => value.get_index(*index),
+        AccessorKey::NegativeIndex(index) => match value.list_len() {
+            Some(len) => match len.checked_sub(*index) {
+                Some(index) => value.get_index(index),
+                None => Err(EvalErrorKind::IndexOutOfBounds { len }),
+            },
+            None => Err(not_a_list(value)),
+        },
+        AccessorKey::Slice(_) | AccessorKey::KeyWildcard | AccessorKey::IndexWildcard => {
+            Err(EvalErrorKind::MultipleValues)
+        }
+    }
+}
+
+/// The error for indexing into a value without a [`Accessible::list_len`].
+fn not_a_list(value: &dyn Accessible) -> EvalErrorKind {
+    match value.map_keys() {
+        Some(_) => EvalErrorKind::NumericIndexInMap,
+        None => EvalErrorKind::NotIndexable,
+    }
+}
+
+/// The error for a `.*` on a value without [`Accessible::map_keys`].
+fn not_a_map(value: &dyn Accessible) -> EvalErrorKind {
+    match value.list_len() {
+        Some(_) => EvalErrorKind::StringKeyInList,
+        None => EvalErrorKind::NotIndexable,
+    }
+}
+
+impl<T: Accessible, S: BuildHasher> Accessible for HashMap<String, T, S> {
+    fn get_key(&self, key: &str) -> Result<&dyn Accessible, EvalErrorKind> {
+        match self.get(key) {
+            Some(value) => Ok(value),
+            None => Err(EvalErrorKind::MissingKey),
+        }
+    }
+
+    fn get_index(&self, _index: usize) -> Result<&dyn Accessible, EvalErrorKind> {
+        Err(EvalErrorKind::NumericIndexInMap)
+    }
+
+    fn map_keys(&self) -> Option<Vec<&str>> {
+        let mut keys: Vec<_> = self.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        Some(keys)
+    }
+}
+
+impl<T: Accessible> Accessible for BTreeMap<String, T> {
+    fn get_key(&self, key: &str) -> Result<&dyn Accessible, EvalErrorKind> {
+        match self.get(key) {
+            Some(value) => Ok(value),
+            None => Err(EvalErrorKind::MissingKey),
+        }
+    }
+
+    fn get_index(&self, _index: usize) -> Result<&dyn Accessible, EvalErrorKind> {
+        Err(EvalErrorKind::NumericIndexInMap)
+    }
+
+    fn map_keys(&self) -> Option<Vec<&str>> {
+        Some(self.keys().map(String::as_str).collect())
+    }
+}
+
+impl<T: Accessible> Accessible for [T] {
+    fn get_key(&self, _key: &str) -> Result<&dyn Accessible, EvalErrorKind> {
+        Err(EvalErrorKind::StringKeyInList)
+    }
+
+    fn get_index(&self, index: usize) -> Result<&dyn Accessible, EvalErrorKind> {
+        match self.get(index) {
+            Some(value) => Ok(value),
+            None => Err(EvalErrorKind::IndexOutOfBounds { len: self.len() }),
+        }
+    }
+
+    fn list_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T: Accessible> Accessible for Vec<T> {
+    fn get_key(&self, key: &str) -> Result<&dyn Accessible, EvalErrorKind> {
+        self.as_slice().get_key(key)
+    }
+
+    fn get_index(&self, index: usize) -> Result<&dyn Accessible, EvalErrorKind> {
+        self.as_slice().get_index(index)
+    }
+
+    fn list_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T: Accessible, const N: usize> Accessible for [T; N] {
+    fn get_key(&self, key: &str) -> Result<&dyn Accessible, EvalErrorKind> {
+        self.as_slice().get_key(key)
+    }
+
+    fn get_index(&self, index: usize) -> Result<&dyn Accessible, EvalErrorKind> {
+        self.as_slice().get_index(index)
+    }
+
+    fn list_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T: Accessible + ?Sized> Accessible for &T {
+    fn get_key(&self, key: &str) -> Result<&dyn Accessible, EvalErrorKind> {
+        (**self).get_key(key)
+    }
+
+    fn get_index(&self, index: usize) -> Result<&dyn Accessible, EvalErrorKind> {
+        (**self).get_index(index)
+    }
+
+    fn list_len(&self) -> Option<usize> {
+        (**self).list_len()
+    }
+
+    fn map_keys(&self) -> Option<Vec<&str>> {
+        (**self).map_keys()
+    }
+
+    fn as_scalar(&self) -> Option<Scalar<'_>> {
+        (**self).as_scalar()
+    }
+}
+
+impl<T: Accessible + ?Sized> Accessible for Box<T> {
+    fn get_key(&self, key: &str) -> Result<&dyn Accessible, EvalErrorKind> {
+        (**self).get_key(key)
+    }
+
+    fn get_index(&self, index: usize) -> Result<&dyn Accessible, EvalErrorKind> {
+        (**self).get_index(index)
+    }
+
+    fn list_len(&self) -> Option<usize> {
+        (**self).list_len()
+    }
+
+    fn map_keys(&self) -> Option<Vec<&str>> {
+        (**self).map_keys()
+    }
+
+    fn as_scalar(&self) -> Option<Scalar<'_>> {
+        (**self).as_scalar()
+    }
+}
+
+impl<T: Accessible> Accessible for Option<T> {
+    fn get_key(&self, key: &str) -> Result<&dyn Accessible, EvalErrorKind> {
+        match self {
+            Some(value) => value.get_key(key),
+            None => Err(EvalErrorKind::NotIndexable),
+        }
+    }
+
+    fn get_index(&self, index: usize) -> Result<&dyn Accessible, EvalErrorKind> {
+        match self {
+            Some(value) => value.get_index(index),
+            None => Err(EvalErrorKind::NotIndexable),
+        }
+    }
+
+    fn list_len(&self) -> Option<usize> {
+        self.as_ref().and_then(Accessible::list_len)
+    }
+
+    fn map_keys(&self) -> Option<Vec<&str>> {
+        self.as_ref().and_then(Accessible::map_keys)
+    }
+
+    fn as_scalar(&self) -> Option<Scalar<'_>> {
+        match self {
+            Some(value) => value.as_scalar(),
+            None => Some(Scalar::Null),
+        }
+    }
+}
+
+impl Accessible for str {
+    fn as_scalar(&self) -> Option<Scalar<'_>> {
+        Some(Scalar::String(Cow::Borrowed(self)))
+    }
+}
+
+impl Accessible for String {
+    fn as_scalar(&self) -> Option<Scalar<'_>> {
+        Some(Scalar::String(Cow::Borrowed(self)))
+    }
+}
+
+impl Accessible for char {
+    fn as_scalar(&self) -> Option<Scalar<'_>> {
+        Some(Scalar::String(Cow::Owned(self.to_string())))
+    }
+}
+
+impl Accessible for bool {
+    fn as_scalar(&self) -> Option<Scalar<'_>> {
+        Some(Scalar::Bool(*self))
+    }
+}
+
+impl Accessible for u128 {
+    fn as_scalar(&self) -> Option<Scalar<'_>> {
+        match i128::try_from(*self) {
+            Ok(value) => Some(Scalar::Integer(value)),
+            Err(_) => Some(Scalar::String(Cow::Owned(self.to_string()))),
+        }
+    }
+}
+
+macro_rules! impl_scalar {
+    ($variant:ident as $target:ty: $($ty:ty),* $(,)?) => {
+        $(impl Accessible for $ty {
+            fn as_scalar(&self) -> Option<Scalar<'_>> {
+                Some(Scalar::$variant(*self as $target))
+            }
+        })*
+    };
+}
+
+impl_scalar!(Integer as i128: u8, u16, u32, u64, usize, i8, i16, i32, i64, i128, isize);
+impl_scalar!(Float as f64: f64);
+
+impl Accessible for f32 {
+    fn as_scalar(&self) -> Option<Scalar<'_>> {
+        // Widening adds digits, e.g. `0.1` becomes `0.10000000149011612`, so the shortest
+        // representation of the f32 is kept instead.
+        let value = self.to_string().parse().unwrap_or(f64::from(*self));
+        Some(Scalar::Float(value))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, HashMap};
+
+    use maplit::{btreemap, hashmap};
+
+    use super::Accessible;
+    use crate::{
+        error::{EvalError, EvalErrorKind},
+        Accessor, AccessorKey, SpannedAccessor,
+    };
+
+    fn is_same(resolved: &dyn Accessible, expected: &dyn Accessible) -> bool {
+        std::ptr::addr_eq(resolved, expected)
+    }
+
+    fn test_data() -> HashMap<String, BTreeMap<String, Vec<String>>> {
+        hashmap! {
+            "event".to_owned() => btreemap! {
+                "tags".to_owned() => vec!["first".to_owned(), "second".to_owned()],
+            },
+        }
+    }
+
+    #[test]
+    fn should_resolve_nested_collections() {
+        let data = test_data();
+
+        let accessor: Accessor = "${event.tags[1]}".parse().unwrap();
+        let resolved = accessor.resolve(&data).unwrap();
+        assert!(is_same(resolved, &data["event"]["tags"][1]));
+
+        let accessor = SpannedAccessor::parse("${event.tags}").unwrap();
+        let resolved = accessor.resolve(&data).unwrap();
+        assert!(is_same(resolved, &data["event"]["tags"]));
+    }
+
+    #[test]
+    fn should_resolve_slices() {
+        let data: &[[u32; 2]] = &[[1, 2], [3, 4]];
+
+        let accessor: Accessor = "${\\u{30}}".parse().unwrap();
+        match accessor.resolve(&data).err().unwrap() {
+            EvalError {
+                kind: EvalErrorKind::StringKeyInList,
+                key: AccessorKey::String(key),
+                position: 0,
+            } if key.as_ref() == "0" => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        let list = hashmap! { "list".to_owned() => data };
+        let accessor: Accessor = "${list[1][0]}".parse().unwrap();
+        let resolved = accessor.resolve(&list).unwrap();
+        assert!(is_same(resolved, &data[1][0]));
+    }
+
+    #[test]
+    fn should_resolve_negative_indices() {
+        let data = test_data();
+
+        let accessor: Accessor = "${event.tags[-1]}".parse().unwrap();
+        let resolved = accessor.resolve(&data).unwrap();
+        assert!(is_same(resolved, &data["event"]["tags"][1]));
+
+        let accessor: Accessor = "${event.tags[-3]}".parse().unwrap();
+        match accessor.resolve(&data).err().unwrap() {
+            EvalError {
+                kind: EvalErrorKind::IndexOutOfBounds { len: 2 },
+                key: AccessorKey::NegativeIndex(3),
+                position: 2,
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        let accessor: Accessor = "${event[-1]}".parse().unwrap();
+        match accessor.resolve(&data).err().unwrap() {
+            EvalError {
+                kind: EvalErrorKind::NumericIndexInMap,
+                position: 1,
+                ..
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_resolve_all_values_of_slices() {
+        let data = hashmap! {
+            "lists".to_owned() => vec![vec![1, 2, 3], vec![4, 5], vec![6]],
+        };
+        let resolve_all = |accessor: &str| {
+            let accessor: Accessor = accessor.parse().unwrap();
+            accessor
+                .resolve_all(&data)
+                .unwrap()
+                .iter()
+                .map(|value| value.as_scalar().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(vec!["2", "3"], resolve_all("${lists[0][1:]}"));
+        assert_eq!(vec!["1", "3"], resolve_all("${lists[0][::2]}"));
+        assert_eq!(vec!["3", "5", "6"], resolve_all("${lists[:][-1]}"));
+        assert_eq!(vec!["1", "2"], resolve_all("${lists[0][-5:-1]}"));
+        assert_eq!(vec!["4"], resolve_all("${lists[1][0]}"));
+        assert!(resolve_all("${lists[2:1]}").is_empty());
+
+        let accessor: Accessor = "${lists[:][1]}".parse().unwrap();
+        match accessor.resolve_all(&data).err().unwrap() {
+            EvalError {
+                kind: EvalErrorKind::IndexOutOfBounds { len: 1 },
+                key: AccessorKey::Numeric(1),
+                position: 2,
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        let accessor: Accessor = "${lists[1:]}".parse().unwrap();
+        match accessor.resolve(&data).err().unwrap() {
+            EvalError {
+                kind: EvalErrorKind::MultipleValues,
+                position: 1,
+                ..
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        let accessor: Accessor = "${event[1:]}".parse().unwrap();
+        match accessor.resolve_all(&test_data()).err().unwrap() {
+            EvalError {
+                kind: EvalErrorKind::NumericIndexInMap,
+                position: 1,
+                ..
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_resolve_paths_of_wildcards() {
+        let data = hashmap! {
+            "labels".to_owned() => hashmap! {
+                "team".to_owned() => vec!["core".to_owned()],
+                "env".to_owned() => vec!["prod".to_owned(), "eu".to_owned()],
+            },
+        };
+
+        let accessor: Accessor = "${labels.*[*]}".parse().unwrap();
+        let resolved: Vec<_> = accessor
+            .resolve_paths(&data)
+            .unwrap()
+            .into_iter()
+            .map(|(path, value)| (path.to_string(), value.as_scalar().unwrap().to_string()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("${labels.env[0]}".to_owned(), "prod".to_owned()),
+                ("${labels.env[1]}".to_owned(), "eu".to_owned()),
+                ("${labels.team[0]}".to_owned(), "core".to_owned()),
+            ],
+            resolved
+        );
+
+        let accessor: Accessor = "${labels.env[*]}".parse().unwrap();
+        let (path, value) = &accessor.resolve_paths(&data).unwrap()[1];
+        assert_eq!("${labels.env[1]}", path.to_string());
+        assert!(is_same(*value, &data["labels"]["env"][1]));
+
+        let accessor: Accessor = "${labels[*]}".parse().unwrap();
+        match accessor.resolve_all(&data).err().unwrap() {
+            EvalError {
+                kind: EvalErrorKind::NumericIndexInMap,
+                key: AccessorKey::IndexWildcard,
+                position: 1,
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        let accessor: Accessor = "${labels.env.*}".parse().unwrap();
+        match accessor.resolve_all(&data).err().unwrap() {
+            EvalError {
+                kind: EvalErrorKind::StringKeyInList,
+                key: AccessorKey::KeyWildcard,
+                position: 2,
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_resolve_missing_key() {
+        let data = test_data();
+
+        let accessor: Accessor = "${event.labels[0]}".parse().unwrap();
+        match accessor.resolve(&data).err().unwrap() {
+            EvalError {
+                kind: EvalErrorKind::MissingKey,
+                key: AccessorKey::String(key),
+                position: 1,
+            } if key.as_ref() == "labels" => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_resolve_index_out_of_bounds() {
+        let data = test_data();
+
+        let accessor: Accessor = "${event.tags[2]}".parse().unwrap();
+        match accessor.resolve(&data).err().unwrap() {
+            EvalError {
+                kind: EvalErrorKind::IndexOutOfBounds { len: 2 },
+                key: AccessorKey::Numeric(2),
+                position: 2,
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_resolve_into_scalar() {
+        let data = test_data();
+
+        let accessor: Accessor = "${event.tags[0].name}".parse().unwrap();
+        match accessor.resolve(&data).err().unwrap() {
+            EvalError {
+                kind: EvalErrorKind::NotIndexable,
+                position: 3,
+                ..
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        let accessor: Accessor = "${event[0]}".parse().unwrap();
+        match accessor.resolve(&data).err().unwrap() {
+            EvalError {
+                kind: EvalErrorKind::NumericIndexInMap,
+                position: 1,
+                ..
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+}
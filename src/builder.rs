@@ -0,0 +1,369 @@
+use std::collections::HashMap;
+
+use crate::{
+    error::{PathNodeBuilderError, PathNodeBuilderErrorKind},
+    validation::{FieldType, PathNode},
+};
+
+/// Builds a [`PathNode`] tree, collecting invalid definitions until [`PathNodeBuilder::build`].
+///
+/// ```
+/// use accessor_rs::validation::{FieldType, PathNode};
+///
+/// let schema = PathNode::node()
+///     .child(
+///         "event",
+///         PathNode::node()
+///             .typed_field("created_ms", FieldType::Integer)
+///             .object_root("payload")
+///             .child("tags", PathNode::list(PathNode::node().field("name"), None)),
+///     )
+///     .root("item")
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct PathNodeBuilder {
+    node: PathNode,
+    errors: Vec<PathNodeBuilderError>,
+}
+
+impl PathNode {
+    /// Starts a [`PathNode::Node`] without any children.
+    pub fn node() -> PathNodeBuilder {
+        PathNodeBuilder::from(PathNode::Node {
+            children: HashMap::new(),
+        })
+    }
+
+    pub fn list(item: impl Into<PathNodeBuilder>, max_len: Option<usize>) -> PathNodeBuilder {
+        item.into().wrap(|item| PathNode::List {
+            item: Box::new(item),
+            max_len,
+        })
+    }
+
+    pub fn map(value: impl Into<PathNodeBuilder>) -> PathNodeBuilder {
+        value.into().wrap(|value| PathNode::Map {
+            value: Box::new(value),
+        })
+    }
+
+    pub fn deprecated(
+        node: impl Into<PathNodeBuilder>,
+        replacement: Option<&str>,
+    ) -> PathNodeBuilder {
+        node.into().wrap(|node| PathNode::Deprecated {
+            node: Box::new(node),
+            replacement: replacement.map(str::to_owned),
+        })
+    }
+}
+
+impl PathNodeBuilder {
+    /// Adds a child to the node, its definition errors are reported below `key`.
+    pub fn child(mut self, key: &str, child: impl Into<PathNodeBuilder>) -> Self {
+        let PathNodeBuilder { node, errors } = child.into();
+        self.errors.extend(errors.into_iter().map(|mut err| {
+            err.path.insert(0, key.to_owned());
+            err
+        }));
+
+        let kind = match &mut self.node {
+            PathNode::Node { children } if children.contains_key(key) => {
+                PathNodeBuilderErrorKind::DuplicateKey
+            }
+            PathNode::Node { children } => {
+                children.insert(key.to_owned(), node);
+                return self;
+            }
+            _ => PathNodeBuilderErrorKind::NotANode,
+        };
+        self.errors.push(PathNodeBuilderError {
+            kind,
+            path: vec![key.to_owned()],
+        });
+        self
+    }
+
+    /// Adds a field, that can hold any value.
+    pub fn field(self, key: &str) -> Self {
+        self.typed_field(key, FieldType::Any)
+    }
+
+    pub fn typed_field(self, key: &str, field_type: FieldType) -> Self {
+        self.child(key, PathNode::KnownField(field_type))
+    }
+
+    pub fn root(self, key: &str) -> Self {
+        self.child(key, PathNode::Root)
+    }
+
+    pub fn object_root(self, key: &str) -> Self {
+        self.child(key, PathNode::ObjectRoot)
+    }
+
+    /// Returns the tree, or all invalid definitions found while building it.
+    pub fn build(self) -> Result<PathNode, Vec<PathNodeBuilderError>> {
+        match self.errors.is_empty() {
+            true => Ok(self.node),
+            false => Err(self.errors),
+        }
+    }
+
+    fn wrap(self, wrap: impl FnOnce(PathNode) -> PathNode) -> Self {
+        PathNodeBuilder {
+            node: wrap(self.node),
+            errors: self.errors,
+        }
+    }
+}
+
+impl From<PathNode> for PathNodeBuilder {
+    fn from(node: PathNode) -> Self {
+        PathNodeBuilder {
+            node,
+            errors: vec![],
+        }
+    }
+}
+
+/// Builds a [`PathNode`](crate::validation::PathNode) tree, that reads like the data it
+/// describes. Evaluates to the result of [`PathNodeBuilder::build`].
+///
+/// * `{ "key" => value, .. }` is a node with the given children.
+/// * `[value]` and `[value; max_len]` are lists.
+/// * `map(value)` is a map with arbitrary keys.
+/// * `deprecated(value)` and `deprecated(value, "replacement")` are deprecated nodes.
+/// * `root`, `object_root`, `string`, `integer`, `float`, `bool`, `timestamp` and `any` are the
+///   leaves.
+/// * `(expr)` is any expression convertible into a [`PathNodeBuilder`].
+///
+/// ```
+/// use accessor_rs::path_node;
+///
+/// let schema = path_node! {
+///     "event" => {
+///         "created_ms" => integer,
+///         "payload" => object_root,
+///         "tags" => [{ "name" => string }],
+///         "labels" => map(string),
+///     },
+///     "item" => root,
+/// }
+/// .unwrap();
+/// ```
+#[macro_export]
+macro_rules! path_node {
+    (@children $builder:expr;) => {
+        $builder
+    };
+    (@children $builder:expr; $key:literal => $name:ident ( $($args:tt)* ) $(, $($rest:tt)*)?) => {
+        $crate::path_node!(
+            @children $builder.child($key, $crate::path_node!(@value $name($($args)*)));
+            $($($rest)*)?
+        )
+    };
+    (@children $builder:expr; $key:literal => $value:tt $(, $($rest:tt)*)?) => {
+        $crate::path_node!(
+            @children $builder.child($key, $crate::path_node!(@value $value));
+            $($($rest)*)?
+        )
+    };
+    (@value { $($children:tt)* }) => {
+        $crate::path_node!(@children $crate::validation::PathNode::node(); $($children)*)
+    };
+    (@value [ $name:ident ( $($args:tt)* ) $(; $max_len:expr)? ]) => {
+        $crate::path_node!(@list $crate::path_node!(@value $name($($args)*)) $(, $max_len)?)
+    };
+    (@value [ $item:tt $(; $max_len:expr)? ]) => {
+        $crate::path_node!(@list $crate::path_node!(@value $item) $(, $max_len)?)
+    };
+    (@value map ( $($value:tt)+ )) => {
+        $crate::validation::PathNode::map($crate::path_node!(@value $($value)+))
+    };
+    (@value deprecated ( $name:ident ( $($args:tt)* ) $(, $replacement:expr)? )) => {
+        $crate::path_node!(
+            @deprecated $crate::path_node!(@value $name($($args)*)) $(, $replacement)?
+        )
+    };
+    (@value deprecated ( $node:tt $(, $replacement:expr)? )) => {
+        $crate::path_node!(@deprecated $crate::path_node!(@value $node) $(, $replacement)?)
+    };
+    (@value root) => {
+        $crate::validation::PathNodeBuilder::from($crate::validation::PathNode::Root)
+    };
+    (@value object_root) => {
+        $crate::validation::PathNodeBuilder::from($crate::validation::PathNode::ObjectRoot)
+    };
+    (@value string) => { $crate::path_node!(@field String) };
+    (@value integer) => { $crate::path_node!(@field Integer) };
+    (@value float) => { $crate::path_node!(@field Float) };
+    (@value bool) => { $crate::path_node!(@field Bool) };
+    (@value timestamp) => { $crate::path_node!(@field Timestamp) };
+    (@value any) => { $crate::path_node!(@field Any) };
+    (@value ( $value:expr )) => {
+        $crate::validation::PathNodeBuilder::from($value)
+    };
+    (@field $field_type:ident) => {
+        $crate::validation::PathNodeBuilder::from($crate::validation::PathNode::KnownField(
+            $crate::validation::FieldType::$field_type,
+        ))
+    };
+    (@list $item:expr) => {
+        $crate::validation::PathNode::list($item, ::core::option::Option::None)
+    };
+    (@list $item:expr, $max_len:expr) => {
+        $crate::validation::PathNode::list($item, ::core::option::Option::Some($max_len))
+    };
+    (@deprecated $node:expr) => {
+        $crate::validation::PathNode::deprecated($node, ::core::option::Option::None)
+    };
+    (@deprecated $node:expr, $replacement:expr) => {
+        $crate::validation::PathNode::deprecated($node, ::core::option::Option::Some($replacement))
+    };
+    ($($children:tt)*) => {
+        $crate::path_node!(@value { $($children)* }).build()
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use maplit::hashmap;
+
+    use crate::{
+        error::{PathNodeBuilderError, PathNodeBuilderErrorKind},
+        validation::{FieldType, PathNode},
+    };
+
+    fn expected_tree() -> PathNode {
+        PathNode::Node {
+            children: hashmap! {
+                "event".to_owned() => PathNode::Node { children: hashmap! {
+                    "created_ms".to_owned() => PathNode::KnownField(FieldType::Integer),
+                    "payload".to_owned() => PathNode::ObjectRoot,
+                    "tags".to_owned() => PathNode::List {
+                        item: Box::new(PathNode::Node { children: hashmap! {
+                            "name".to_owned() => PathNode::KnownField(FieldType::String),
+                        }}),
+                        max_len: None,
+                    },
+                    "position".to_owned() => PathNode::List {
+                        item: Box::new(PathNode::KnownField(FieldType::Float)),
+                        max_len: Some(2),
+                    },
+                    "labels".to_owned() => PathNode::Map {
+                        value: Box::new(PathNode::KnownField(FieldType::String)),
+                    },
+                    "created".to_owned() => PathNode::Deprecated {
+                        node: Box::new(PathNode::KnownField(FieldType::Timestamp)),
+                        replacement: Some("created_ms".to_owned()),
+                    },
+                }},
+                "item".to_owned() => PathNode::Root,
+                "extra".to_owned() => PathNode::KnownField(FieldType::Any),
+            },
+        }
+    }
+
+    #[test]
+    fn should_build_path_node() {
+        let node = PathNode::node()
+            .child(
+                "event",
+                PathNode::node()
+                    .typed_field("created_ms", FieldType::Integer)
+                    .object_root("payload")
+                    .child(
+                        "tags",
+                        PathNode::list(
+                            PathNode::node().typed_field("name", FieldType::String),
+                            None,
+                        ),
+                    )
+                    .child(
+                        "position",
+                        PathNode::list(PathNode::KnownField(FieldType::Float), Some(2)),
+                    )
+                    .child(
+                        "labels",
+                        PathNode::map(PathNode::KnownField(FieldType::String)),
+                    )
+                    .child(
+                        "created",
+                        PathNode::deprecated(
+                            PathNode::KnownField(FieldType::Timestamp),
+                            Some("created_ms"),
+                        ),
+                    ),
+            )
+            .root("item")
+            .field("extra")
+            .build()
+            .unwrap();
+
+        assert_eq!(expected_tree(), node);
+    }
+
+    #[test]
+    fn should_build_path_node_with_macro() {
+        let node = path_node! {
+            "event" => {
+                "created_ms" => integer,
+                "payload" => object_root,
+                "tags" => [{ "name" => string }],
+                "position" => [float; 2],
+                "labels" => map(string),
+                "created" => deprecated(timestamp, "created_ms"),
+            },
+            "item" => root,
+            "extra" => (PathNode::KnownField(FieldType::Any)),
+        }
+        .unwrap();
+
+        assert_eq!(expected_tree(), node);
+    }
+
+    #[test]
+    fn should_report_duplicate_keys() {
+        let errors = path_node! {
+            "event" => {
+                "created_ms" => integer,
+                "tags" => [{ "name" => string, "name" => any }],
+                "created_ms" => timestamp,
+            },
+            "item" => root,
+            "item" => object_root,
+        }
+        .unwrap_err();
+
+        match errors.as_slice() {
+            [PathNodeBuilderError {
+                kind: PathNodeBuilderErrorKind::DuplicateKey,
+                path: first,
+            }, PathNodeBuilderError {
+                kind: PathNodeBuilderErrorKind::DuplicateKey,
+                path: second,
+            }, PathNodeBuilderError {
+                kind: PathNodeBuilderErrorKind::DuplicateKey,
+                path: third,
+            }] => {
+                assert_eq!(&["event", "tags", "name"], first.as_slice());
+                assert_eq!(&["event", "created_ms"], second.as_slice());
+                assert_eq!(&["item"], third.as_slice());
+            }
+            err => unreachable!("{:?}", err),
+        }
+
+        match PathNode::list(PathNode::Root, None).root("item").build() {
+            Err(errors) => match errors.as_slice() {
+                [PathNodeBuilderError {
+                    kind: PathNodeBuilderErrorKind::NotANode,
+                    ..
+                }] => {}
+                err => unreachable!("{:?}", err),
+            },
+            ok => unreachable!("{:?}", ok),
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use std::ops::Range;
+
+use nom_locate::LocatedSpan;
+
+use crate::{
+    parser::{
+        span_of_range, take_numeric_key, take_string_with_escape_until, RESERVED_RAW_LITERAL,
+        RESERVED_TOKEN,
+    },
+    validation::{edit_distance, PathNode},
+    AccessorKey, AccessorParserSpan,
+};
+
+/// A key, that can be inserted at the cursor, see [`complete_at`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion<'a> {
+    key: String,
+    insert_text: String,
+    span: AccessorParserSpan,
+    node: &'a PathNode,
+}
+
+impl<'a> Completion<'a> {
+    /// The key as stored in the schema.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// The key escaped for the template, replacing the text at [`Completion::span`].
+    pub fn insert_text(&self) -> &str {
+        &self.insert_text
+    }
+
+    /// The span of the partial key under the cursor, without its leading `.`.
+    pub fn span(&self) -> AccessorParserSpan {
+        self.span
+    }
+
+    /// The node, the key leads to.
+    pub fn node(&self) -> &'a PathNode {
+        self.node
+    }
+}
+
+/// Completes the key of the accessor under the byte offset `cursor` of `template`.
+///
+/// The template may be incomplete, e.g. `${event.cr`. The keys allowed by the `schema` are ranked
+/// by the edit distance between the typed part and the start of each key, the best match first.
+pub fn complete_at<'a>(template: &str, cursor: usize, schema: &'a PathNode) -> Vec<Completion<'a>> {
+    if !template.is_char_boundary(cursor) {
+        return vec![];
+    }
+
+    let Some(accessor_start) = accessor_start(&template[..cursor]) else {
+        return vec![];
+    };
+    let Some((keys, key_range)) = current_key(template, accessor_start + 2, cursor) else {
+        return vec![];
+    };
+    let Some(children) = schema.node_at(&keys).and_then(PathNode::children) else {
+        return vec![];
+    };
+
+    let is_root = keys.is_empty();
+    let typed = template[key_range.start..cursor].trim_start_matches('"');
+    let typed_len = typed.chars().count();
+    let span = span_of_range(template, key_range);
+
+    let mut completions: Vec<_> = children
+        .iter()
+        .map(|(key, node)| {
+            let prefix: String = key.chars().take(typed_len).collect();
+            let rank = (edit_distance(typed, &prefix), edit_distance(typed, key));
+
+            let mut insert_text = AccessorKey::from(key.clone()).to_template(is_root);
+            if !is_root {
+                insert_text.remove(0);
+            }
+
+            let completion = Completion {
+                key: key.clone(),
+                insert_text,
+                span,
+                node,
+            };
+            (rank, completion)
+        })
+        .collect();
+    completions.sort_by(|(a_rank, a), (b_rank, b)| a_rank.cmp(b_rank).then(a.key.cmp(&b.key)));
+
+    completions
+        .into_iter()
+        .map(|(_rank, completion)| completion)
+        .collect()
+}
+
+/// The start of the accessor, that is still open at the end of `text`.
+fn accessor_start(text: &str) -> Option<usize> {
+    let mut start = None;
+    let mut quoted = false;
+    let mut chars = text.char_indices();
+
+    while let Some((idx, ch)) = chars.next() {
+        match ch {
+            '\\' => {
+                chars.next();
+            }
+            '$' if start.is_none() && text[idx..].starts_with("${") => {
+                start = Some(idx);
+                chars.next();
+            }
+            '"' if start.is_some() => quoted = !quoted,
+            '}' if start.is_some() && !quoted => start = None,
+            _ => {}
+        }
+    }
+
+    // The cursor has to be behind the opening `${`.
+    start.filter(|start| start + 2 <= text.len())
+}
+
+/// The keys in front of the cursor, and the byte range of the key the cursor is in.
+fn current_key(
+    template: &str,
+    mut key_start: usize,
+    cursor: usize,
+) -> Option<(Vec<AccessorKey>, Range<usize>)> {
+    let mut keys = vec![];
+
+    loop {
+        let (key_end, key) = take_key(template, key_start)?;
+        if cursor <= key_end {
+            return Some((keys, key_start..key_end));
+        }
+        // The root key is never a wildcard, like in the parser.
+        match &template[key_start..key_end] {
+            "*" if !keys.is_empty() => keys.push(AccessorKey::KeyWildcard),
+            _ => keys.push(AccessorKey::from(key)),
+        }
+
+        let mut separator = key_end;
+        while template[separator..].starts_with('[') {
+            let close = separator + template[separator..].find(']')?;
+            // Indices can't be completed.
+            if cursor <= close {
+                return None;
+            }
+            let (_, index) = take_numeric_key(template[separator..=close].into()).ok()?;
+            keys.push(index);
+            separator = close + 1;
+        }
+
+        if cursor <= separator || !template[separator..].starts_with('.') {
+            return None;
+        }
+        key_start = separator + 1;
+    }
+}
+
+/// Takes the possibly incomplete, plain or quoted key starting at `start`.
+fn take_key(template: &str, start: usize) -> Option<(usize, String)> {
+    let (quoted, reserved_token) = match template[start..].starts_with('"') {
+        true => (true, RESERVED_RAW_LITERAL),
+        false => (false, RESERVED_TOKEN),
+    };
+    let content_start = if quoted { start + 1 } else { start };
+
+    let mut content_end = template.len();
+    let mut chars = template[content_start..].char_indices();
+    while let Some((idx, ch)) = chars.next() {
+        match ch {
+            '\\' => {
+                chars.next();
+            }
+            ch if reserved_token.contains(&ch) || ch == '\n' || (!quoted && ch == ' ') => {
+                content_end = content_start + idx;
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    let content = LocatedSpan::new(&template[content_start..content_end]);
+    let (_, key) = take_string_with_escape_until(|_| false, reserved_token)(content).ok()?;
+
+    match quoted && template[content_end..].starts_with('"') {
+        true => Some((content_end + 1, key)),
+        false => Some((content_end, key)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::complete_at;
+    use crate::{
+        path_node,
+        validation::{FieldType, PathNode},
+        AccessorParserSpan, SourcePosition,
+    };
+
+    fn test_path_tree() -> PathNode {
+        path_node! {
+            "event" => {
+                "created_ms" => integer,
+                "creator" => string,
+                "id" => integer,
+                "key.with.dots" => bool,
+                "tags" => [{ "name" => string }],
+            },
+            "item" => root,
+            "sources" => map({ "host" => string, "port" => integer }),
+        }
+        .unwrap()
+    }
+
+    fn complete(template: &str, cursor: usize) -> Vec<(String, String, usize, usize)> {
+        let schema = test_path_tree();
+        complete_at(template, cursor, &schema)
+            .into_iter()
+            .map(|completion| {
+                let span = completion.span();
+                (
+                    completion.key().to_owned(),
+                    completion.insert_text().to_owned(),
+                    span.start(),
+                    span.end(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_complete_incomplete_accessor() {
+        let completions = complete("Created: ${event.cr", 19);
+
+        assert_eq!(
+            vec!["creator", "created_ms", "id", "tags", "key.with.dots"],
+            completions
+                .iter()
+                .map(|(key, ..)| key.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            ("creator".to_owned(), "creator".to_owned(), 17, 19),
+            completions[0]
+        );
+        assert_eq!(
+            (
+                "key.with.dots".to_owned(),
+                "\"key.with.dots\"".to_owned(),
+                17,
+                19
+            ),
+            completions[4]
+        );
+    }
+
+    #[test]
+    fn should_complete_key_in_the_middle_of_template() {
+        let template = "Item: ${item}\nTag: ${event.tags[0].nme} - ${sources.local.p}";
+
+        assert_eq!(
+            vec![("name".to_owned(), "name".to_owned(), 35, 38)],
+            complete(template, 36)
+        );
+        assert_eq!(
+            vec![("name".to_owned(), "name".to_owned(), 17, 19)],
+            complete("${event.tags[-1].na", 19)
+        );
+        assert_eq!(
+            ("port".to_owned(), "port".to_owned(), 12, 13),
+            complete("${sources.*.p", 13)[0]
+        );
+
+        let completions = complete(template, 59);
+        assert_eq!(
+            ("port".to_owned(), "port".to_owned(), 58, 59),
+            completions[0]
+        );
+        assert_eq!(
+            ("host".to_owned(), "host".to_owned(), 58, 59),
+            completions[1]
+        );
+
+        let schema = test_path_tree();
+        let completion = complete_at(template, 36, &schema).remove(0);
+        assert_eq!(&PathNode::KnownField(FieldType::String), completion.node());
+        match completion.span() {
+            AccessorParserSpan {
+                start_position:
+                    SourcePosition {
+                        line: 2,
+                        column: 22,
+                    },
+                end_position:
+                    SourcePosition {
+                        line: 2,
+                        column: 25,
+                    },
+                ..
+            } => {}
+            span => unreachable!("{:?}", span),
+        }
+    }
+
+    #[test]
+    fn should_complete_root_keys() {
+        let completions = complete("${", 2);
+        assert_eq!(
+            vec!["item", "event", "sources"],
+            completions
+                .iter()
+                .map(|(key, ..)| key.as_str())
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!("item", complete("${it}", 4)[0].0);
+    }
+
+    #[test]
+    fn should_not_complete_outside_of_accessors() {
+        assert!(complete("${event.id} text", 14).is_empty());
+        assert!(complete("\\${event.", 9).is_empty());
+        assert!(complete("${event.tags[0", 14).is_empty());
+        assert!(complete("${item.", 7).is_empty());
+        assert!(complete("$", 1).is_empty());
+    }
+}
@@ -0,0 +1,57 @@
+use std::fmt::{Display, Write};
+
+use crate::{error::Severity, AccessorParserSpan};
+
+/// Renders `message` with its `severity` in a compiler like style, underlining `span` in the line
+/// of `source` it starts in.
+///
+/// ```text
+/// error: unknown key
+///  --> 3:21
+///   |
+/// 3 | Target: ${_variables.target4}
+///   |                     ^^^^^^^^
+///   = help: did you mean `target1`?
+/// ```
+pub(crate) fn render(
+    source: &str,
+    severity: Severity,
+    message: &impl Display,
+    span: AccessorParserSpan,
+    help: Option<String>,
+) -> String {
+    let start = span.start_position;
+    let end = span.end_position;
+    let line_number = start.line.to_string();
+    let gutter = " ".repeat(line_number.len());
+
+    let mut out = String::new();
+    let _ = writeln!(out, "{severity}: {message}");
+    let _ = writeln!(out, "{gutter}--> {}:{}", start.line, start.column);
+
+    // A span pointing behind the end of the source has no line to show.
+    if let Some(line) = source.lines().nth(start.line as usize - 1) {
+        let line_len = line.chars().count();
+        let offset = start.column - 1;
+        let underline = if end.line == start.line {
+            end.column.saturating_sub(start.column)
+        } else {
+            line_len.saturating_sub(offset)
+        };
+
+        let _ = writeln!(out, "{gutter} |");
+        let _ = writeln!(out, "{line_number} | {line}");
+        let _ = writeln!(
+            out,
+            "{gutter} | {}{}",
+            " ".repeat(offset),
+            "^".repeat(underline.max(1))
+        );
+    }
+
+    if let Some(help) = help {
+        let _ = writeln!(out, "{gutter} = help: {help}");
+    }
+
+    out
+}
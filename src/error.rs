@@ -1,7 +1,12 @@
+use std::{error::Error, fmt};
+
 use nom::error::{ErrorKind, ParseError};
 use nom_locate::LocatedSpan;
 
-use crate::AccessorParserSpan;
+use crate::{
+    diagnostic, parser::span_of_chars, validation::FieldType, Accessor, AccessorKey,
+    AccessorParserSpan,
+};
 
 #[derive(Clone, Copy, Debug)]
 pub struct AccessorParserError {
@@ -17,8 +22,28 @@ impl AccessorParserError {
     pub fn span(&self) -> AccessorParserSpan {
         self.span
     }
+
+    /// Renders the error together with the offending line of `source`, the parsed template.
+    pub fn render_diagnostic(&self, source: &str) -> String {
+        diagnostic::render(
+            source,
+            Severity::Error,
+            &self.kind,
+            self.span,
+            self.kind.help(),
+        )
+    }
+}
+
+impl fmt::Display for AccessorParserError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let position = self.span.start_position;
+        write!(f, "{} at {}:{}", self.kind, position.line, position.column)
+    }
 }
 
+impl Error for AccessorParserError {}
+
 #[derive(Clone, Copy, Debug)]
 pub enum AccessorParserErrorKind {
     InvalidCharacter(char),
@@ -28,9 +53,79 @@ pub enum AccessorParserErrorKind {
     MissingClosingBracket,
     InvalidAccessor,
     NotANumber,
+    NegativeZeroIndex,
+    InvalidSlice,
+    TrailingInput,
     Unknown(ErrorKind),
 }
 
+impl AccessorParserErrorKind {
+    pub fn help(&self) -> Option<String> {
+        match self {
+            AccessorParserErrorKind::InvalidCharacter(ch) => Some(format!(
+                "reserved characters have to be escaped, e.g. `\\{ch}`"
+            )),
+            AccessorParserErrorKind::InvalidEscapeCharacter(_) => Some(
+                "supported escape sequences are `\\n`, `\\t`, `\\r`, `\\\\`, `\\u{..}` \
+                 and escaped reserved characters"
+                    .to_owned(),
+            ),
+            AccessorParserErrorKind::InvalidUnicode(_) => {
+                Some("unicode escapes look like `\\u{1F600}`".to_owned())
+            }
+            AccessorParserErrorKind::InvalidAccessorKey => {
+                Some("keys start with `.`, indices are written as `[0]`".to_owned())
+            }
+            AccessorParserErrorKind::MissingClosingBracket => {
+                Some("add the missing closing bracket".to_owned())
+            }
+            AccessorParserErrorKind::NotANumber => Some(
+                "indices have to be integers, e.g. `[0]`, or `[-1]` for the last item".to_owned(),
+            ),
+            AccessorParserErrorKind::NegativeZeroIndex => {
+                Some("the last item is `[-1]`, the first one `[0]`".to_owned())
+            }
+            AccessorParserErrorKind::InvalidSlice => Some(
+                "slices look like `[start:end:step]`, e.g. `[1:3]` or `[::2]`, \
+                 the step has to be positive"
+                    .to_owned(),
+            ),
+            AccessorParserErrorKind::TrailingInput => {
+                Some("only a single accessor like `${event.created_ms}` is allowed".to_owned())
+            }
+            AccessorParserErrorKind::InvalidAccessor | AccessorParserErrorKind::Unknown(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for AccessorParserErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccessorParserErrorKind::InvalidCharacter(ch) => {
+                write!(f, "invalid character `{}`", ch.escape_default())
+            }
+            AccessorParserErrorKind::InvalidEscapeCharacter(ch) => {
+                write!(f, "invalid escape sequence `\\{}`", ch.escape_default())
+            }
+            AccessorParserErrorKind::InvalidUnicode(err) => write!(f, "{err}"),
+            AccessorParserErrorKind::InvalidAccessorKey => f.write_str("invalid accessor key"),
+            AccessorParserErrorKind::MissingClosingBracket => {
+                f.write_str("missing closing bracket")
+            }
+            AccessorParserErrorKind::InvalidAccessor => f.write_str("invalid accessor"),
+            AccessorParserErrorKind::NotANumber => f.write_str("index is not a number"),
+            AccessorParserErrorKind::NegativeZeroIndex => f.write_str("index `-0` is ambiguous"),
+            AccessorParserErrorKind::InvalidSlice => f.write_str("invalid slice"),
+            AccessorParserErrorKind::TrailingInput => {
+                f.write_str("unexpected input after the accessor")
+            }
+            AccessorParserErrorKind::Unknown(kind) => {
+                write!(f, "unexpected parser error: {}", kind.description())
+            }
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum InvalidUnicodeError {
     MissingOpeningBracket,
@@ -40,15 +135,31 @@ pub enum InvalidUnicodeError {
     InvalidCodePoint,
 }
 
+impl fmt::Display for InvalidUnicodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidUnicodeError::MissingOpeningBracket => {
+                f.write_str("missing `{` in unicode escape")
+            }
+            InvalidUnicodeError::MissingClosingBracket => {
+                f.write_str("missing `}` in unicode escape")
+            }
+            InvalidUnicodeError::InvalidCodeLength => {
+                f.write_str("unicode escape must have between 2 and 8 hexadecimal digits")
+            }
+            InvalidUnicodeError::InvalidHexadecimal => {
+                f.write_str("invalid hexadecimal number in unicode escape")
+            }
+            InvalidUnicodeError::InvalidCodePoint => f.write_str("invalid unicode code point"),
+        }
+    }
+}
+
 impl<'input> ParseError<LocatedSpan<&'input str>> for AccessorParserError {
     fn from_error_kind(input: LocatedSpan<&'input str>, kind: nom::error::ErrorKind) -> Self {
-        let span_start = input.get_utf8_column();
         AccessorParserError {
             kind: AccessorParserErrorKind::Unknown(kind),
-            span: AccessorParserSpan {
-                start: span_start,
-                end: span_start + 1,
-            },
+            span: span_of_chars(input, 1),
         }
     }
 
@@ -57,7 +168,7 @@ impl<'input> ParseError<LocatedSpan<&'input str>> for AccessorParserError {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AccessorValidationError {
     pub(crate) kind: AccessorValidationErrorKind,
     pub(crate) span: AccessorParserSpan,
@@ -71,14 +182,525 @@ impl AccessorValidationError {
     pub fn span(&self) -> AccessorParserSpan {
         self.span
     }
+
+    pub fn severity(&self) -> Severity {
+        self.kind.severity()
+    }
+
+    /// Renders the error together with the offending line of `source`, the validated template.
+    pub fn render_diagnostic(&self, source: &str) -> String {
+        diagnostic::render(
+            source,
+            self.severity(),
+            &self.kind,
+            self.span,
+            self.kind.help(),
+        )
+    }
+}
+
+impl fmt::Display for AccessorValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let position = self.span.start_position;
+        write!(f, "{} at {}:{}", self.kind, position.line, position.column)
+    }
 }
 
-#[derive(Debug, Clone)]
+impl Error for AccessorValidationError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AccessorValidationErrorKind {
     NumericIndexInMap,
     InvalidRoot,
     InvalidObjectRoot,
     NotIndexable,
-    UnknownKey { possible_keys: Vec<String> },
+    UnknownKey {
+        possible_keys: Vec<String>,
+    },
+    NotStringRepresentable,
+    StringKeyInList,
+    IndexOutOfBounds {
+        max_len: usize,
+    },
+    TypeMismatch {
+        expected: FieldType,
+        found: FieldType,
+    },
+    DeprecatedField {
+        replacement: Option<String>,
+    },
+    SuspiciousInString {
+        field_type: FieldType,
+    },
+}
+
+impl AccessorValidationErrorKind {
+    pub fn severity(&self) -> Severity {
+        match self {
+            AccessorValidationErrorKind::DeprecatedField { .. } => Severity::Warning,
+            AccessorValidationErrorKind::SuspiciousInString {
+                field_type: FieldType::Timestamp,
+            } => Severity::Warning,
+            AccessorValidationErrorKind::SuspiciousInString { .. } => Severity::Info,
+            _ => Severity::Error,
+        }
+    }
+
+    pub fn help(&self) -> Option<String> {
+        match self {
+            AccessorValidationErrorKind::NumericIndexInMap => {
+                Some("maps can only be accessed with keys like `.name`".to_owned())
+            }
+            AccessorValidationErrorKind::NotIndexable => {
+                Some("remove the keys following the field".to_owned())
+            }
+            AccessorValidationErrorKind::UnknownKey { possible_keys } => possible_keys
+                .first()
+                .map(|key| format!("did you mean `{key}`?")),
+            AccessorValidationErrorKind::NotStringRepresentable => {
+                Some("access a single field of the value instead".to_owned())
+            }
+            AccessorValidationErrorKind::StringKeyInList => {
+                Some("lists can only be accessed with indices like `[0]`".to_owned())
+            }
+            AccessorValidationErrorKind::IndexOutOfBounds { max_len } => {
+                Some(format!("the list has at most {max_len} items"))
+            }
+            AccessorValidationErrorKind::TypeMismatch { expected, .. } => {
+                Some(format!("use a field of type {expected}"))
+            }
+            AccessorValidationErrorKind::DeprecatedField { replacement } => replacement
+                .as_ref()
+                .map(|replacement| format!("use `{replacement}` instead")),
+            AccessorValidationErrorKind::SuspiciousInString { field_type } => Some(format!(
+                "the {field_type} is rendered without any formatting"
+            )),
+            AccessorValidationErrorKind::InvalidRoot
+            | AccessorValidationErrorKind::InvalidObjectRoot => None,
+        }
+    }
+}
+
+impl fmt::Display for AccessorValidationErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccessorValidationErrorKind::NumericIndexInMap => {
+                f.write_str("numeric index used on a map")
+            }
+            AccessorValidationErrorKind::InvalidRoot => f.write_str("invalid root"),
+            AccessorValidationErrorKind::InvalidObjectRoot => f.write_str("invalid object root"),
+            AccessorValidationErrorKind::NotIndexable => f.write_str("field can't be indexed"),
+            AccessorValidationErrorKind::UnknownKey { .. } => f.write_str("unknown key"),
+            AccessorValidationErrorKind::NotStringRepresentable => {
+                f.write_str("value can't be represented as a string")
+            }
+            AccessorValidationErrorKind::StringKeyInList => {
+                f.write_str("string key used on a list")
+            }
+            AccessorValidationErrorKind::IndexOutOfBounds { .. } => {
+                f.write_str("index out of bounds")
+            }
+            AccessorValidationErrorKind::TypeMismatch { expected, found } => {
+                write!(f, "expected a field of type {expected}, found {found}")
+            }
+            AccessorValidationErrorKind::DeprecatedField { .. } => {
+                f.write_str("field is deprecated")
+            }
+            AccessorValidationErrorKind::SuspiciousInString { field_type } => {
+                write!(f, "{field_type} field used in a string")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => f.write_str("error"),
+            Severity::Warning => f.write_str("warning"),
+            Severity::Info => f.write_str("info"),
+        }
+    }
+}
+
+/// All problems found while validating an accessor or an interpolator, in the order of their
+/// occurrence.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationReport {
+    pub(crate) entries: Vec<AccessorValidationError>,
+}
+
+impl ValidationReport {
+    pub fn entries(&self) -> &[AccessorValidationError] {
+        &self.entries
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &AccessorValidationError> {
+        self.with_severity(Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &AccessorValidationError> {
+        self.with_severity(Severity::Warning)
+    }
+
+    pub fn with_severity(
+        &self,
+        severity: Severity,
+    ) -> impl Iterator<Item = &AccessorValidationError> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.severity() == severity)
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    /// Whether the validation passed, i.e. the report holds no errors but maybe warnings.
+    pub fn is_ok(&self) -> bool {
+        !self.has_errors()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Renders every entry of the report, see [`AccessorValidationError::render_diagnostic`].
+    pub fn render_diagnostic(&self, source: &str) -> String {
+        self.entries
+            .iter()
+            .map(|entry| entry.render_diagnostic(source))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Entries are only added once, even when a wildcard reaches the same problem through
+    /// several children.
+    pub(crate) fn push(&mut self, entry: AccessorValidationError) {
+        if !self.entries.contains(&entry) {
+            self.entries.push(entry);
+        }
+    }
+}
+
+impl IntoIterator for ValidationReport {
+    type Item = AccessorValidationError;
+    type IntoIter = std::vec::IntoIter<AccessorValidationError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, entry) in self.entries.iter().enumerate() {
+            if idx > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{}: {entry}", entry.severity())?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ValidationReport {}
+
+/// An invalid definition found by [`crate::validation::PathNodeBuilder`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathNodeBuilderError {
+    pub(crate) kind: PathNodeBuilderErrorKind,
+    pub(crate) path: Vec<String>,
+}
+
+impl PathNodeBuilderError {
+    pub fn kind(&self) -> PathNodeBuilderErrorKind {
+        self.kind
+    }
+
+    /// The keys leading to the invalid child, starting at the root.
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+}
+
+impl fmt::Display for PathNodeBuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} `{}`", self.kind, self.path.join("."))
+    }
+}
+
+impl Error for PathNodeBuilderError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathNodeBuilderErrorKind {
+    DuplicateKey,
+    /// Children can only be added to a [`crate::validation::PathNode::Node`].
+    NotANode,
+}
+
+impl fmt::Display for PathNodeBuilderErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathNodeBuilderErrorKind::DuplicateKey => f.write_str("duplicate key"),
+            PathNodeBuilderErrorKind::NotANode => f.write_str("child added to a non-node"),
+        }
+    }
+}
+
+/// A construct of a JSON Schema, that can't be turned into a [`crate::validation::PathNode`].
+#[cfg(feature = "json-schema")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonSchemaError {
+    pub(crate) kind: JsonSchemaErrorKind,
+    pub(crate) pointer: String,
+}
+
+#[cfg(feature = "json-schema")]
+impl JsonSchemaError {
+    pub fn kind(&self) -> &JsonSchemaErrorKind {
+        &self.kind
+    }
+
+    /// The JSON pointer to the offending schema inside the document.
+    pub fn pointer(&self) -> &str {
+        &self.pointer
+    }
+}
+
+#[cfg(feature = "json-schema")]
+impl fmt::Display for JsonSchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at `{}`", self.kind, self.pointer)
+    }
+}
+
+#[cfg(feature = "json-schema")]
+impl Error for JsonSchemaError {}
+
+#[cfg(feature = "json-schema")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonSchemaErrorKind {
+    UnsupportedKeyword(String),
+    UnsupportedType(String),
+    UnresolvedReference(String),
+    InvalidSchema,
+}
+
+#[cfg(feature = "json-schema")]
+impl fmt::Display for JsonSchemaErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonSchemaErrorKind::UnsupportedKeyword(keyword) => {
+                write!(f, "unsupported keyword `{keyword}`")
+            }
+            JsonSchemaErrorKind::UnsupportedType(ty) => write!(f, "unsupported type `{ty}`"),
+            JsonSchemaErrorKind::UnresolvedReference(reference) => {
+                write!(f, "unresolved reference `{reference}`")
+            }
+            JsonSchemaErrorKind::InvalidSchema => f.write_str("invalid schema"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvalError {
+    pub(crate) kind: EvalErrorKind,
+    pub(crate) key: AccessorKey,
+    pub(crate) position: usize,
+}
+
+impl EvalError {
+    pub fn kind(&self) -> EvalErrorKind {
+        self.kind
+    }
+
+    /// The key of the accessor that could not be resolved.
+    pub fn key(&self) -> &AccessorKey {
+        &self.key
+    }
+
+    /// The index of the failing key inside the accessor.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.key {
+            AccessorKey::String(key) => write!(f, "{} for key `{key}`", self.kind)?,
+            AccessorKey::KeyWildcard => write!(f, "{} for key `*`", self.kind)?,
+            key => write!(f, "{} for index `{}`", self.kind, key.to_template(false))?,
+        }
+        write!(f, " at position {}", self.position)
+    }
+}
+
+impl Error for EvalError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvalErrorKind {
+    MissingKey,
+    IndexOutOfBounds {
+        len: usize,
+    },
+    NumericIndexInMap,
+    StringKeyInList,
+    NotIndexable,
+    /// A single value was requested, but the accessor matches multiple ones.
+    MultipleValues,
+}
+
+impl fmt::Display for EvalErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalErrorKind::MissingKey => f.write_str("missing key"),
+            EvalErrorKind::IndexOutOfBounds { len } => {
+                write!(f, "index out of bounds for length {len}")
+            }
+            EvalErrorKind::NumericIndexInMap => f.write_str("numeric index used on a map"),
+            EvalErrorKind::StringKeyInList => f.write_str("string key used on a list"),
+            EvalErrorKind::NotIndexable => f.write_str("value can't be indexed"),
+            EvalErrorKind::MultipleValues => f.write_str("accessor matches multiple values"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderError {
+    pub(crate) kind: RenderErrorKind,
+    pub(crate) accessor: Accessor,
+    pub(crate) span: Option<Box<AccessorParserSpan>>,
+}
+
+impl RenderError {
+    pub fn kind(&self) -> &RenderErrorKind {
+        &self.kind
+    }
+
+    /// The accessor of the segment, that could not be rendered.
+    pub fn accessor(&self) -> &Accessor {
+        &self.accessor
+    }
+
+    /// The span of the accessor, when a [`crate::string_interpolator::SpannedStringInterpolator`]
+    /// was rendered.
+    pub fn span(&self) -> Option<AccessorParserSpan> {
+        self.span.as_deref().copied()
+    }
+
+    /// Renders the error together with the offending line of `source`, the rendered template.
+    /// Errors without a [span](RenderError::span) have no line to show.
+    pub fn render_diagnostic(&self, source: &str) -> Option<String> {
+        let span = self.span()?;
+        Some(diagnostic::render(
+            source,
+            Severity::Error,
+            self,
+            span,
+            None,
+        ))
+    }
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            RenderErrorKind::Eval(err) => write!(f, "failed to resolve {}: {err}", self.accessor),
+            RenderErrorKind::NotStringRepresentable => write!(
+                f,
+                "value of {} can't be represented as a string",
+                self.accessor
+            ),
+        }
+    }
+}
+
+impl Error for RenderError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            RenderErrorKind::Eval(err) => Some(err),
+            RenderErrorKind::NotStringRepresentable => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenderErrorKind {
+    Eval(EvalError),
     NotStringRepresentable,
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        string_interpolator::SpannedStringInterpolator,
+        validation::{FieldType, PathNode},
+        SpannedAccessor,
+    };
+
+    #[test]
+    fn should_display_errors() {
+        let err = SpannedAccessor::parse("${event[abc]}").unwrap_err();
+        assert_eq!("index is not a number at 1:9", err.to_string());
+
+        let err = SpannedAccessor::parse("${event.\\q}").unwrap_err();
+        assert_eq!("invalid escape sequence `\\q` at 1:9", err.to_string());
+    }
+
+    #[test]
+    fn should_render_parser_diagnostic() {
+        let source = "${event[abc]}";
+        let err = SpannedAccessor::parse(source).unwrap_err();
+
+        assert_eq!(
+            "error: index is not a number\n \
+             --> 1:9\n  \
+             |\n\
+             1 | ${event[abc]}\n  \
+             |         ^^^\n  \
+             = help: indices have to be integers, e.g. `[0]`, or `[-1]` for the last item\n",
+            err.render_diagnostic(source)
+        );
+    }
+
+    #[test]
+    fn should_render_validation_diagnostic() {
+        let schema = PathNode::Node {
+            children: [(
+                "event".to_owned(),
+                PathNode::Node {
+                    children: [(
+                        "created_ms".to_owned(),
+                        PathNode::KnownField(FieldType::Integer),
+                    )]
+                    .into(),
+                },
+            )]
+            .into(),
+        };
+        let source = "Event:\n  created at ${event.create_ms}\n";
+        let interpolator = SpannedStringInterpolator::parse(source).unwrap();
+        let report = schema.validate_interpolator(&interpolator);
+        let errors = report.entries();
+
+        assert_eq!("unknown key at 2:21", errors[0].to_string());
+        assert_eq!(
+            "error: unknown key\n \
+             --> 2:21\n  \
+             |\n\
+             2 |   created at ${event.create_ms}\n  \
+             |                     ^^^^^^^^^^\n  \
+             = help: did you mean `created_ms`?\n",
+            errors[0].render_diagnostic(source)
+        );
+    }
+}
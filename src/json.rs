@@ -0,0 +1,568 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeSet, HashMap},
+};
+
+use serde_json::Value;
+
+use crate::{
+    accessible::{Accessible, Scalar},
+    error::{EvalError, EvalErrorKind},
+    validation::{FieldType, HasPathNode, PathNode},
+    Accessor, AccessorKey,
+};
+
+/// The number of distinct keys, above which an inferred object is collapsed into an
+/// [`PathNode::ObjectRoot`] by default.
+const DEFAULT_MAX_INFERRED_KEYS: usize = 64;
+
+impl HasPathNode for Value {
+    fn path_node() -> PathNode {
+        PathNode::ObjectRoot
+    }
+}
+
+impl PathNode {
+    /// Merges the structure of all samples into a single tree, collapsing objects with more than
+    /// 64 distinct keys. See [`PathNode::infer_from_samples_with_max_keys`].
+    pub fn infer_from_samples(samples: &[Value]) -> PathNode {
+        Self::infer_from_samples_with_max_keys(samples, DEFAULT_MAX_INFERRED_KEYS)
+    }
+
+    /// Merges the structure of all samples into a single tree.
+    ///
+    /// Objects become a [`PathNode::Node`] with all keys seen in any sample, unless they have more
+    /// than `max_keys` distinct keys, which are collapsed into a [`PathNode::ObjectRoot`]. Arrays
+    /// become a [`PathNode::List`] of their merged items, and scalars a [`PathNode::KnownField`].
+    /// Values, whose structure differs between samples, accept any path.
+    pub fn infer_from_samples_with_max_keys(samples: &[Value], max_keys: usize) -> PathNode {
+        infer(samples.iter().collect(), max_keys)
+    }
+}
+
+fn infer(values: Vec<&Value>, max_keys: usize) -> PathNode {
+    // A missing value says nothing about the structure of the others.
+    let values: Vec<_> = values
+        .into_iter()
+        .filter(|value| !value.is_null())
+        .collect();
+
+    if values.is_empty() {
+        return PathNode::KnownField(FieldType::Any);
+    }
+
+    if values.iter().all(|value| value.is_object()) {
+        let objects: Vec<_> = values
+            .iter()
+            .filter_map(|value| value.as_object())
+            .collect();
+        let keys: BTreeSet<_> = objects.iter().flat_map(|object| object.keys()).collect();
+        if keys.len() > max_keys {
+            return PathNode::ObjectRoot;
+        }
+
+        let children = keys
+            .into_iter()
+            .map(|key| {
+                let values = objects
+                    .iter()
+                    .filter_map(|object| object.get(key))
+                    .collect();
+                (key.clone(), infer(values, max_keys))
+            })
+            .collect::<HashMap<_, _>>();
+        return PathNode::Node { children };
+    }
+
+    if values.iter().all(|value| value.is_array()) {
+        let items: Vec<_> = values
+            .iter()
+            .filter_map(|value| value.as_array())
+            .flatten()
+            .collect();
+        let item = match items.is_empty() {
+            true => PathNode::Root,
+            false => infer(items, max_keys),
+        };
+        return PathNode::List {
+            item: Box::new(item),
+            max_len: None,
+        };
+    }
+
+    let mut field_types = values.iter().map(|value| match value {
+        Value::Bool(_) => Some(FieldType::Bool),
+        Value::Number(number) if number.is_f64() => Some(FieldType::Float),
+        Value::Number(_) => Some(FieldType::Integer),
+        Value::String(_) => Some(FieldType::String),
+        Value::Null | Value::Array(_) | Value::Object(_) => None,
+    });
+    let Some(Some(first)) = field_types.next() else {
+        return PathNode::Root;
+    };
+
+    let mut field_type = first;
+    for other in field_types {
+        field_type = match (field_type, other) {
+            // Containers mixed with scalars have no common structure.
+            (_, None) => return PathNode::Root,
+            (field_type, Some(other)) if field_type == other => field_type,
+            (
+                FieldType::Integer | FieldType::Float,
+                Some(FieldType::Integer | FieldType::Float),
+            ) => FieldType::Float,
+            _ => FieldType::Any,
+        };
+    }
+
+    PathNode::KnownField(field_type)
+}
+
+impl Accessible for Value {
+    fn get_key(&self, key: &str) -> Result<&dyn Accessible, EvalErrorKind> {
+        match self {
+            Value::Object(map) => match map.get(key) {
+                Some(value) => Ok(value),
+                None => Err(EvalErrorKind::MissingKey),
+            },
+            Value::Array(_) => Err(EvalErrorKind::StringKeyInList),
+            _ => Err(EvalErrorKind::NotIndexable),
+        }
+    }
+
+    fn get_index(&self, index: usize) -> Result<&dyn Accessible, EvalErrorKind> {
+        match self {
+            Value::Array(list) => match list.get(index) {
+                Some(value) => Ok(value),
+                None => Err(EvalErrorKind::IndexOutOfBounds { len: list.len() }),
+            },
+            Value::Object(_) => Err(EvalErrorKind::NumericIndexInMap),
+            _ => Err(EvalErrorKind::NotIndexable),
+        }
+    }
+
+    fn list_len(&self) -> Option<usize> {
+        self.as_array().map(Vec::len)
+    }
+
+    fn map_keys(&self) -> Option<Vec<&str>> {
+        self.as_object()
+            .map(|map| map.keys().map(String::as_str).collect())
+    }
+
+    fn as_scalar(&self) -> Option<Scalar<'_>> {
+        match self {
+            Value::Null => Some(Scalar::Null),
+            Value::Bool(value) => Some(Scalar::Bool(*value)),
+            Value::Number(number) => match (number.as_i64(), number.as_u64(), number.as_f64()) {
+                (Some(value), _, _) => Some(Scalar::Integer(value.into())),
+                (_, Some(value), _) => Some(Scalar::Integer(value.into())),
+                (_, _, Some(value)) => Some(Scalar::Float(value)),
+                _ => Some(Scalar::String(Cow::Owned(number.to_string()))),
+            },
+            Value::String(value) => Some(Scalar::String(Cow::Borrowed(value))),
+            Value::Array(_) | Value::Object(_) => None,
+        }
+    }
+}
+
+impl Accessor {
+    pub fn get<'value>(&self, value: &'value Value) -> Result<&'value Value, EvalError> {
+        let mut value = value;
+        for (position, key) in self.keys.iter().enumerate() {
+            value = get_key(value, key).map_err(|kind| EvalError {
+                kind,
+                key: key.clone(),
+                position,
+            })?;
+        }
+
+        Ok(value)
+    }
+
+    pub fn get_mut<'value>(
+        &self,
+        value: &'value mut Value,
+    ) -> Result<&'value mut Value, EvalError> {
+        let mut value = value;
+        for (position, key) in self.keys.iter().enumerate() {
+            value = get_key_mut(value, key).map_err(|kind| EvalError {
+                kind,
+                key: key.clone(),
+                position,
+            })?;
+        }
+
+        Ok(value)
+    }
+}
+
+fn get_key<'value>(
+    value: &'value Value,
+    key: &AccessorKey,
+) -> Result<&'value Value, EvalErrorKind> {
+    match (value, key) {
+        (Value::Object(map), AccessorKey::String(key)) => {
+            map.get(key.as_ref()).ok_or(EvalErrorKind::MissingKey)
+        }
+        (Value::Array(list), AccessorKey::Numeric(index)) => list
+            .get(*index)
+            .ok_or(EvalErrorKind::IndexOutOfBounds { len: list.len() }),
+        (Value::Array(list), AccessorKey::NegativeIndex(index)) => list
+            .len()
+            .checked_sub(*index)
+            .and_then(|index| list.get(index))
+            .ok_or(EvalErrorKind::IndexOutOfBounds { len: list.len() }),
+        (Value::Array(_), AccessorKey::Slice(_) | AccessorKey::IndexWildcard)
+        | (Value::Object(_), AccessorKey::KeyWildcard) => Err(EvalErrorKind::MultipleValues),
+        (
+            Value::Object(_),
+            AccessorKey::Numeric(_)
+            | AccessorKey::NegativeIndex(_)
+            | AccessorKey::Slice(_)
+            | AccessorKey::IndexWildcard,
+        ) => Err(EvalErrorKind::NumericIndexInMap),
+        (Value::Array(_), AccessorKey::String(_) | AccessorKey::KeyWildcard) => {
+            Err(EvalErrorKind::StringKeyInList)
+        }
+        _ => Err(EvalErrorKind::NotIndexable),
+    }
+}
+
+fn get_key_mut<'value>(
+    value: &'value mut Value,
+    key: &AccessorKey,
+) -> Result<&'value mut Value, EvalErrorKind> {
+    match (value, key) {
+        (Value::Object(map), AccessorKey::String(key)) => {
+            map.get_mut(key.as_ref()).ok_or(EvalErrorKind::MissingKey)
+        }
+        (Value::Array(list), AccessorKey::Numeric(index)) => {
+            let len = list.len();
+            list.get_mut(*index)
+                .ok_or(EvalErrorKind::IndexOutOfBounds { len })
+        }
+        (Value::Array(list), AccessorKey::NegativeIndex(index)) => {
+            let len = list.len();
+            len.checked_sub(*index)
+                .and_then(|index| list.get_mut(index))
+                .ok_or(EvalErrorKind::IndexOutOfBounds { len })
+        }
+        (Value::Array(_), AccessorKey::Slice(_) | AccessorKey::IndexWildcard)
+        | (Value::Object(_), AccessorKey::KeyWildcard) => Err(EvalErrorKind::MultipleValues),
+        (
+            Value::Object(_),
+            AccessorKey::Numeric(_)
+            | AccessorKey::NegativeIndex(_)
+            | AccessorKey::Slice(_)
+            | AccessorKey::IndexWildcard,
+        ) => Err(EvalErrorKind::NumericIndexInMap),
+        (Value::Array(_), AccessorKey::String(_) | AccessorKey::KeyWildcard) => {
+            Err(EvalErrorKind::StringKeyInList)
+        }
+        _ => Err(EvalErrorKind::NotIndexable),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use maplit::hashmap;
+    use serde_json::{json, Value};
+
+    use crate::{
+        accessible::Accessible,
+        error::{EvalError, EvalErrorKind, RenderError, RenderErrorKind},
+        string_interpolator::StringInterpolator,
+        validation::{FieldType, PathNode},
+        Accessor, AccessorKey,
+    };
+
+    fn test_event() -> Value {
+        json!({
+            "event": {
+                "created_ms": 1234,
+                "tags": [{ "name": "first" }, { "name": "second" }],
+                "key.with.dots": true,
+                "ratio": 0.5,
+                "deleted_at": null,
+            },
+            "item": "pippo",
+        })
+    }
+
+    #[test]
+    fn should_get_value() {
+        let value = test_event();
+
+        let accessor: Accessor = "${event.created_ms}".parse().unwrap();
+        assert_eq!(&json!(1234), accessor.get(&value).unwrap());
+
+        let accessor: Accessor = "${event.tags[1].name}".parse().unwrap();
+        assert_eq!(&json!("second"), accessor.get(&value).unwrap());
+
+        let accessor: Accessor = "${event.\"key.with.dots\"}".parse().unwrap();
+        assert_eq!(&json!(true), accessor.get(&value).unwrap());
+
+        let accessor: Accessor = "${item}".parse().unwrap();
+        assert_eq!(&json!("pippo"), accessor.get(&value).unwrap());
+
+        let accessor: Accessor = "${event.tags[-1].name}".parse().unwrap();
+        assert_eq!(&json!("second"), accessor.get(&value).unwrap());
+
+        let resolved: &dyn Accessible = accessor.resolve(&value).unwrap();
+        let expected: &dyn Accessible = accessor.get(&value).unwrap();
+        assert!(std::ptr::addr_eq(resolved, expected));
+    }
+
+    #[test]
+    fn should_get_value_mutably() {
+        let mut value = test_event();
+
+        let accessor: Accessor = "${event.tags[0].name}".parse().unwrap();
+        *accessor.get_mut(&mut value).unwrap() = json!("changed");
+        assert_eq!(&json!("changed"), accessor.get(&value).unwrap());
+
+        let accessor: Accessor = "${event.tags[-2].name}".parse().unwrap();
+        assert_eq!(&json!("changed"), accessor.get_mut(&mut value).unwrap());
+    }
+
+    #[test]
+    fn should_fail_on_missing_key() {
+        let value = test_event();
+
+        let accessor: Accessor = "${event.created}".parse().unwrap();
+        match accessor.get(&value).unwrap_err() {
+            EvalError {
+                kind: EvalErrorKind::MissingKey,
+                key: AccessorKey::String(key),
+                position: 1,
+            } if key.as_ref() == "created" => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_fail_on_index_out_of_bounds() {
+        let mut value = test_event();
+
+        let accessor: Accessor = "${event.tags[2].name}".parse().unwrap();
+        match accessor.get(&value).unwrap_err() {
+            EvalError {
+                kind: EvalErrorKind::IndexOutOfBounds { len: 2 },
+                key: AccessorKey::Numeric(2),
+                position: 2,
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        match accessor.get_mut(&mut value).unwrap_err() {
+            EvalError {
+                kind: EvalErrorKind::IndexOutOfBounds { len: 2 },
+                key: AccessorKey::Numeric(2),
+                position: 2,
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        let accessor: Accessor = "${event.tags[-3]}".parse().unwrap();
+        match accessor.get_mut(&mut value).unwrap_err() {
+            EvalError {
+                kind: EvalErrorKind::IndexOutOfBounds { len: 2 },
+                key: AccessorKey::NegativeIndex(3),
+                position: 2,
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_fail_on_wrong_container() {
+        let value = test_event();
+
+        let accessor: Accessor = "${event[0]}".parse().unwrap();
+        match accessor.get(&value).unwrap_err() {
+            EvalError {
+                kind: EvalErrorKind::NumericIndexInMap,
+                position: 1,
+                ..
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        let accessor: Accessor = "${event.tags.name}".parse().unwrap();
+        match accessor.get(&value).unwrap_err() {
+            EvalError {
+                kind: EvalErrorKind::StringKeyInList,
+                position: 2,
+                ..
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        let accessor: Accessor = "${event.tags[:1].name}".parse().unwrap();
+        match accessor.get(&value).unwrap_err() {
+            EvalError {
+                kind: EvalErrorKind::MultipleValues,
+                position: 2,
+                ..
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_index_into_scalar() {
+        let value = test_event();
+
+        let accessor: Accessor = "${event.created_ms.value}".parse().unwrap();
+        match accessor.get(&value).unwrap_err() {
+            EvalError {
+                kind: EvalErrorKind::NotIndexable,
+                key: AccessorKey::String(key),
+                position: 2,
+            } if key.as_ref() == "value" => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_resolve_value_as_accessible() {
+        let value = test_event();
+
+        let accessor: Accessor = "${event.tags[1].name}".parse().unwrap();
+        let resolved: &dyn Accessible = accessor.resolve(&value).unwrap();
+        let expected: &dyn Accessible = accessor.get(&value).unwrap();
+        assert!(std::ptr::addr_eq(resolved, expected));
+
+        let accessor: Accessor = "${event.tags.name}".parse().unwrap();
+        assert_eq!(
+            accessor.get(&value).unwrap_err(),
+            accessor.resolve(&value).err().unwrap()
+        );
+    }
+
+    #[test]
+    fn should_render_interpolator() {
+        let value = test_event();
+
+        let interpolator: StringInterpolator =
+            "${event.created_ms} - ${item}: ${event.tags[0].name}"
+                .parse()
+                .unwrap();
+        assert_eq!("1234 - pippo: first", interpolator.render(&value).unwrap());
+
+        let interpolator: StringInterpolator =
+            "${event.\"key.with.dots\"}, ${event.ratio}, '${event.deleted_at}'"
+                .parse()
+                .unwrap();
+        assert_eq!("true, 0.5, ''", interpolator.render(&value).unwrap());
+
+        let interpolator: StringInterpolator = "${event.tags[*].name}".parse().unwrap();
+        assert_eq!("first, second", interpolator.render(&value).unwrap());
+    }
+
+    #[test]
+    fn should_resolve_paths_of_wildcards() {
+        let value = json!({ "sources": { "b": { "port": 2 }, "a": { "port": 1 } } });
+
+        let accessor: Accessor = "${sources.*.port}".parse().unwrap();
+        let resolved: Vec<_> = accessor
+            .resolve_paths(&value)
+            .unwrap()
+            .into_iter()
+            .map(|(path, value)| (path.to_string(), value.as_scalar().unwrap().to_string()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("${sources.a.port}".to_owned(), "1".to_owned()),
+                ("${sources.b.port}".to_owned(), "2".to_owned()),
+            ],
+            resolved
+        );
+
+        match accessor.get(&value).unwrap_err() {
+            EvalError {
+                kind: EvalErrorKind::MultipleValues,
+                key: AccessorKey::KeyWildcard,
+                position: 1,
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_render_non_scalar() {
+        let value = test_event();
+
+        let interpolator: StringInterpolator = "tags: ${event.tags}".parse().unwrap();
+        match interpolator.render(&value).unwrap_err() {
+            RenderError {
+                kind: RenderErrorKind::NotStringRepresentable,
+                accessor,
+                ..
+            } => assert_eq!("${event.tags}", accessor.to_string()),
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_infer_path_node_from_samples() {
+        let samples = [
+            test_event(),
+            json!({
+                "event": {
+                    "created_ms": 5678,
+                    "tags": [],
+                    "ratio": 1,
+                    "deleted_at": "2024-01-01",
+                    "source": { "host": "localhost" },
+                },
+                "item": 42,
+            }),
+        ];
+
+        let expected = PathNode::Node {
+            children: hashmap! {
+                "event".to_owned() => PathNode::Node { children: hashmap! {
+                    "created_ms".to_owned() => PathNode::KnownField(FieldType::Integer),
+                    "tags".to_owned() => PathNode::List {
+                        item: Box::new(PathNode::Node { children: hashmap! {
+                            "name".to_owned() => PathNode::KnownField(FieldType::String),
+                        }}),
+                        max_len: None,
+                    },
+                    "key.with.dots".to_owned() => PathNode::KnownField(FieldType::Bool),
+                    "ratio".to_owned() => PathNode::KnownField(FieldType::Float),
+                    "deleted_at".to_owned() => PathNode::KnownField(FieldType::String),
+                    "source".to_owned() => PathNode::Node { children: hashmap! {
+                        "host".to_owned() => PathNode::KnownField(FieldType::String),
+                    }},
+                }},
+                "item".to_owned() => PathNode::KnownField(FieldType::Any),
+            },
+        };
+        assert_eq!(expected, PathNode::infer_from_samples(&samples));
+    }
+
+    #[test]
+    fn should_collapse_objects_with_many_keys() {
+        let samples = [
+            json!({ "headers": { "a": 1, "b": 2 }, "list": [1, { "a": 1 }] }),
+            json!({ "headers": { "c": 3 } }),
+        ];
+
+        let expected = PathNode::Node {
+            children: hashmap! {
+                "headers".to_owned() => PathNode::ObjectRoot,
+                "list".to_owned() => PathNode::List {
+                    item: Box::new(PathNode::Root),
+                    max_len: None,
+                },
+            },
+        };
+        assert_eq!(
+            expected,
+            PathNode::infer_from_samples_with_max_keys(&samples, 2)
+        );
+    }
+}
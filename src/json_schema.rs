@@ -0,0 +1,404 @@
+use std::collections::HashMap;
+
+use serde_json::{Map, Value};
+
+use crate::{
+    error::{JsonSchemaError, JsonSchemaErrorKind},
+    validation::{FieldType, PathNode},
+};
+
+/// Keywords, that change the structure of the described data in a way a [`PathNode`] can't
+/// express. Every other unknown keyword only restricts values and is ignored.
+const UNSUPPORTED_KEYWORDS: &[&str] = &[
+    "allOf",
+    "anyOf",
+    "oneOf",
+    "not",
+    "if",
+    "then",
+    "else",
+    "patternProperties",
+    "prefixItems",
+    "dependentSchemas",
+    "unevaluatedProperties",
+    "unevaluatedItems",
+    "$dynamicRef",
+];
+
+impl PathNode {
+    /// Builds the tree from a JSON Schema document, supporting a subset of draft 2020-12.
+    ///
+    /// * Objects with `properties` or `additionalProperties: false` become a [`PathNode::Node`],
+    ///   objects with only a schema in `additionalProperties` a [`PathNode::Map`] and all other
+    ///   objects a [`PathNode::ObjectRoot`]. Properties with a `false` schema are left out. Next
+    ///   to `properties`, `additionalProperties` has to be a boolean, `true` is the same as
+    ///   leaving it out and the node only accepts the listed properties.
+    /// * Arrays become a [`PathNode::List`] of their `items`, bounded by `maxItems`, arrays with
+    ///   `items: false` are always empty.
+    /// * Primitive types become a [`PathNode::KnownField`], strings with a `date-time` or `date`
+    ///   format are timestamps.
+    /// * `$ref` is resolved inside the document, recursive references accept any path.
+    /// * Schemas marked as `deprecated` become a [`PathNode::Deprecated`].
+    ///
+    /// All unsupported constructs of the document are returned at once.
+    pub fn from_json_schema(schema: &Value) -> Result<PathNode, Vec<JsonSchemaError>> {
+        let mut converter = Converter {
+            document: schema,
+            references: vec![],
+            errors: vec![],
+        };
+        let node = converter.convert(schema, String::new());
+
+        match converter.errors.is_empty() {
+            true => Ok(node),
+            false => Err(converter.errors),
+        }
+    }
+}
+
+struct Converter<'a> {
+    document: &'a Value,
+    /// The references currently being resolved, to detect recursion.
+    references: Vec<&'a str>,
+    errors: Vec<JsonSchemaError>,
+}
+
+impl<'a> Converter<'a> {
+    fn convert(&mut self, schema: &'a Value, pointer: String) -> PathNode {
+        let schema = match schema {
+            Value::Bool(true) => return PathNode::Root,
+            Value::Object(schema) => schema,
+            _ => return self.error(JsonSchemaErrorKind::InvalidSchema, pointer),
+        };
+
+        for keyword in UNSUPPORTED_KEYWORDS {
+            if schema.contains_key(*keyword) {
+                let pointer = child_pointer(&pointer, keyword);
+                self.error(
+                    JsonSchemaErrorKind::UnsupportedKeyword(keyword.to_string()),
+                    pointer,
+                );
+            }
+        }
+
+        let node = match schema.get("$ref") {
+            Some(Value::String(reference)) => {
+                self.convert_reference(reference, child_pointer(&pointer, "$ref"))
+            }
+            Some(_) => self.error(
+                JsonSchemaErrorKind::InvalidSchema,
+                child_pointer(&pointer, "$ref"),
+            ),
+            None => self.convert_type(schema, &pointer),
+        };
+
+        match schema.get("deprecated") {
+            Some(Value::Bool(true)) => PathNode::Deprecated {
+                node: Box::new(node),
+                replacement: None,
+            },
+            _ => node,
+        }
+    }
+
+    fn convert_reference(&mut self, reference: &'a str, pointer: String) -> PathNode {
+        // The tree can't describe an infinitely nested structure, so recursion accepts anything.
+        if self.references.contains(&reference) {
+            return PathNode::Root;
+        }
+
+        let target = reference
+            .strip_prefix('#')
+            .and_then(|target| self.document.pointer(target));
+        let Some(target) = target else {
+            return self.error(
+                JsonSchemaErrorKind::UnresolvedReference(reference.to_owned()),
+                pointer,
+            );
+        };
+
+        self.references.push(reference);
+        let node = self.convert(target, reference[1..].to_owned());
+        self.references.pop();
+        node
+    }
+
+    fn convert_type(&mut self, schema: &'a Map<String, Value>, pointer: &str) -> PathNode {
+        let ty = match schema.get("type") {
+            Some(Value::String(ty)) => Some(ty.as_str()),
+            // A nullable value has the structure of its other type.
+            Some(Value::Array(types)) => {
+                let types: Vec<_> = types
+                    .iter()
+                    .filter(|ty| ty.as_str() != Some("null"))
+                    .collect();
+                match types.as_slice() {
+                    [] => Some("null"),
+                    [Value::String(ty)] => Some(ty.as_str()),
+                    _ => {
+                        let types = types
+                            .iter()
+                            .filter_map(|ty| ty.as_str())
+                            .collect::<Vec<_>>();
+                        return self.error(
+                            JsonSchemaErrorKind::UnsupportedType(types.join(", ")),
+                            child_pointer(pointer, "type"),
+                        );
+                    }
+                }
+            }
+            Some(_) => {
+                return self.error(
+                    JsonSchemaErrorKind::InvalidSchema,
+                    child_pointer(pointer, "type"),
+                )
+            }
+            None if schema.contains_key("properties")
+                || schema.contains_key("additionalProperties") =>
+            {
+                Some("object")
+            }
+            None if schema.contains_key("items") => Some("array"),
+            None => None,
+        };
+
+        match ty {
+            Some("object") => self.convert_object(schema, pointer),
+            Some("array") => self.convert_array(schema, pointer),
+            Some("string") => match schema.get("format").and_then(Value::as_str) {
+                Some("date-time" | "date") => PathNode::KnownField(FieldType::Timestamp),
+                _ => PathNode::KnownField(FieldType::String),
+            },
+            Some("integer") => PathNode::KnownField(FieldType::Integer),
+            Some("number") => PathNode::KnownField(FieldType::Float),
+            Some("boolean") => PathNode::KnownField(FieldType::Bool),
+            Some("null") => PathNode::KnownField(FieldType::Any),
+            Some(ty) => self.error(
+                JsonSchemaErrorKind::UnsupportedType(ty.to_owned()),
+                child_pointer(pointer, "type"),
+            ),
+            None => PathNode::Root,
+        }
+    }
+
+    fn convert_object(&mut self, schema: &'a Map<String, Value>, pointer: &str) -> PathNode {
+        let additional_properties = schema.get("additionalProperties");
+
+        match schema.get("properties") {
+            Some(Value::Object(properties)) => {
+                let properties_pointer = child_pointer(pointer, "properties");
+                let mut children = HashMap::with_capacity(properties.len());
+                for (key, property) in properties {
+                    // A property with a `false` schema can never be present.
+                    if *property == Value::Bool(false) {
+                        continue;
+                    }
+                    let node = self.convert(property, child_pointer(&properties_pointer, key));
+                    children.insert(key.clone(), node);
+                }
+                match additional_properties {
+                    None | Some(Value::Bool(_)) => {}
+                    // A node can't describe the schema of further keys next to its known children.
+                    Some(_) => {
+                        self.error(
+                            JsonSchemaErrorKind::UnsupportedKeyword(
+                                "additionalProperties".to_owned(),
+                            ),
+                            child_pointer(pointer, "additionalProperties"),
+                        );
+                    }
+                }
+                PathNode::Node { children }
+            }
+            Some(_) => self.error(
+                JsonSchemaErrorKind::InvalidSchema,
+                child_pointer(pointer, "properties"),
+            ),
+            None => match additional_properties {
+                None | Some(Value::Bool(true)) => PathNode::ObjectRoot,
+                Some(Value::Bool(false)) => PathNode::Node {
+                    children: HashMap::new(),
+                },
+                Some(value) => PathNode::Map {
+                    value: Box::new(
+                        self.convert(value, child_pointer(pointer, "additionalProperties")),
+                    ),
+                },
+            },
+        }
+    }
+
+    fn convert_array(&mut self, schema: &'a Map<String, Value>, pointer: &str) -> PathNode {
+        let item = match schema.get("items") {
+            Some(Value::Bool(false)) => {
+                return PathNode::List {
+                    item: Box::new(PathNode::Root),
+                    max_len: Some(0),
+                }
+            }
+            Some(items) => self.convert(items, child_pointer(pointer, "items")),
+            None => PathNode::Root,
+        };
+        let max_len = match schema.get("maxItems") {
+            Some(max_items) => match max_items.as_u64() {
+                Some(max_items) => Some(max_items as usize),
+                None => {
+                    return self.error(
+                        JsonSchemaErrorKind::InvalidSchema,
+                        child_pointer(pointer, "maxItems"),
+                    )
+                }
+            },
+            None => None,
+        };
+
+        PathNode::List {
+            item: Box::new(item),
+            max_len,
+        }
+    }
+
+    /// Records the error, and accepts anything in place of the offending schema.
+    fn error(&mut self, kind: JsonSchemaErrorKind, pointer: String) -> PathNode {
+        self.errors.push(JsonSchemaError { kind, pointer });
+        PathNode::Root
+    }
+}
+
+fn child_pointer(pointer: &str, key: &str) -> String {
+    format!("{pointer}/{}", key.replace('~', "~0").replace('/', "~1"))
+}
+
+#[cfg(test)]
+mod test {
+    use maplit::hashmap;
+    use serde_json::json;
+
+    use crate::{
+        error::{JsonSchemaError, JsonSchemaErrorKind},
+        validation::{FieldType, PathNode},
+    };
+
+    #[test]
+    fn should_build_path_node_from_json_schema() {
+        let schema = json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "event": {
+                    "type": "object",
+                    "additionalProperties": true,
+                    "properties": {
+                        "created_ms": { "type": "integer" },
+                        "created_at": { "type": "string", "format": "date-time" },
+                        "ratio": { "type": ["number", "null"] },
+                        "tags": {
+                            "type": "array",
+                            "items": { "$ref": "#/$defs/tag" },
+                            "maxItems": 8,
+                        },
+                        "metadata": { "type": "object" },
+                        "labels": { "additionalProperties": { "type": "string" } },
+                        "legacy_id": { "type": "string", "deprecated": true },
+                        "gone": false,
+                        "closed": { "type": "object", "additionalProperties": false },
+                        "empty": { "type": "array", "items": false },
+                    },
+                },
+                "item": {},
+            },
+            "$defs": {
+                "tag": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "parent": { "$ref": "#/$defs/tag" },
+                    },
+                },
+            },
+        });
+
+        let tag = PathNode::Node {
+            children: hashmap! {
+                "name".to_owned() => PathNode::KnownField(FieldType::String),
+                "parent".to_owned() => PathNode::Root,
+            },
+        };
+        let expected = PathNode::Node {
+            children: hashmap! {
+                "event".to_owned() => PathNode::Node { children: hashmap! {
+                    "created_ms".to_owned() => PathNode::KnownField(FieldType::Integer),
+                    "created_at".to_owned() => PathNode::KnownField(FieldType::Timestamp),
+                    "ratio".to_owned() => PathNode::KnownField(FieldType::Float),
+                    "tags".to_owned() => PathNode::List {
+                        item: Box::new(tag),
+                        max_len: Some(8),
+                    },
+                    "metadata".to_owned() => PathNode::ObjectRoot,
+                    "labels".to_owned() => PathNode::Map {
+                        value: Box::new(PathNode::KnownField(FieldType::String)),
+                    },
+                    "legacy_id".to_owned() => PathNode::Deprecated {
+                        node: Box::new(PathNode::KnownField(FieldType::String)),
+                        replacement: None,
+                    },
+                    "closed".to_owned() => PathNode::Node { children: hashmap! {} },
+                    "empty".to_owned() => PathNode::List {
+                        item: Box::new(PathNode::Root),
+                        max_len: Some(0),
+                    },
+                }},
+                "item".to_owned() => PathNode::Root,
+            },
+        };
+
+        assert_eq!(expected, PathNode::from_json_schema(&schema).unwrap());
+    }
+
+    #[test]
+    fn should_report_unsupported_constructs() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "payload": { "oneOf": [{ "type": "string" }, { "type": "integer" }] },
+                "id": { "type": ["string", "integer"] },
+                "a/b": { "$ref": "#/$defs/missing" },
+                "remote": { "$ref": "https://example.com/schema.json" },
+            },
+            "additionalProperties": { "type": "string" },
+        });
+
+        let mut errors = PathNode::from_json_schema(&schema).unwrap_err();
+        errors.sort_by(|a, b| a.pointer().cmp(b.pointer()));
+
+        match errors.as_slice() {
+            [JsonSchemaError {
+                kind: JsonSchemaErrorKind::UnsupportedKeyword(additional),
+                pointer: additional_pointer,
+            }, JsonSchemaError {
+                kind: JsonSchemaErrorKind::UnresolvedReference(missing),
+                pointer: missing_pointer,
+            }, JsonSchemaError {
+                kind: JsonSchemaErrorKind::UnsupportedType(types),
+                pointer: id_pointer,
+            }, JsonSchemaError {
+                kind: JsonSchemaErrorKind::UnsupportedKeyword(keyword),
+                pointer: payload_pointer,
+            }, JsonSchemaError {
+                kind: JsonSchemaErrorKind::UnresolvedReference(_),
+                pointer: remote_pointer,
+            }] => {
+                assert_eq!("additionalProperties", additional);
+                assert_eq!("/additionalProperties", additional_pointer);
+                assert_eq!("#/$defs/missing", missing);
+                assert_eq!("/properties/a~1b/$ref", missing_pointer);
+                assert_eq!("string, integer", types);
+                assert_eq!("/properties/id/type", id_pointer);
+                assert_eq!("oneOf", keyword);
+                assert_eq!("/properties/payload/oneOf", payload_pointer);
+                assert_eq!("/properties/remote/$ref", remote_pointer);
+            }
+            err => unreachable!("{:?}", err),
+        }
+    }
+}
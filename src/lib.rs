@@ -1,15 +1,37 @@
+use std::{fmt, num::NonZeroUsize, str::FromStr};
+
+use error::AccessorParserError;
+
+pub mod accessible;
+pub mod completion;
 pub mod error;
 pub mod parser;
 pub mod string_interpolator;
 pub mod validation;
 
-#[derive(Clone, Debug)]
+mod builder;
+mod diagnostic;
+#[cfg(feature = "serde_json")]
+mod json;
+#[cfg(feature = "json-schema")]
+mod json_schema;
+#[cfg(feature = "miette")]
+mod miette;
+mod paths;
+mod printer;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SpannedAccessor {
     keys: Box<[SpannedAccessorKey]>,
     span: AccessorParserSpan,
 }
 
 impl SpannedAccessor {
+    /// Parses a single accessor, e.g. `${event.created_ms}`. The whole input has to be consumed.
+    pub fn parse(input: &str) -> Result<SpannedAccessor, AccessorParserError> {
+        parser::parse_spanned_accessor(input)
+    }
+
     pub fn keys(&self) -> &[SpannedAccessorKey] {
         &self.keys
     }
@@ -17,9 +39,20 @@ impl SpannedAccessor {
     pub fn span(&self) -> AccessorParserSpan {
         self.span
     }
+
+    /// Whether the accessor can match more than one value, e.g. `${items[1:3]}`.
+    pub fn is_multi_valued(&self) -> bool {
+        self.keys.iter().any(|key| key.key.is_multi_valued())
+    }
 }
 
-#[derive(Clone, Debug)]
+impl fmt::Display for SpannedAccessor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        printer::write_accessor(f, self.keys.iter().map(|key| &key.key))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Accessor {
     keys: Box<[AccessorKey]>,
 }
@@ -28,6 +61,26 @@ impl Accessor {
     pub fn keys(&self) -> &[AccessorKey] {
         &self.keys
     }
+
+    /// Whether the accessor can match more than one value, e.g. `${items[1:3]}` or
+    /// `${items[*].name}`.
+    pub fn is_multi_valued(&self) -> bool {
+        self.keys.iter().any(AccessorKey::is_multi_valued)
+    }
+}
+
+impl fmt::Display for Accessor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        printer::write_accessor(f, self.keys.iter())
+    }
+}
+
+impl FromStr for Accessor {
+    type Err = AccessorParserError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SpannedAccessor::parse(s).map(Into::into)
+    }
 }
 
 impl From<SpannedAccessor> for Accessor {
@@ -38,7 +91,7 @@ impl From<SpannedAccessor> for Accessor {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SpannedAccessorKey {
     key: AccessorKey,
     span: AccessorParserSpan,
@@ -54,10 +107,34 @@ impl SpannedAccessorKey {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum AccessorKey {
     String(Box<str>),
     Numeric(usize),
+    /// An index counting from the end of a list, `[-1]` is stored as `NegativeIndex(1)`.
+    NegativeIndex(usize),
+    Slice(Slice),
+    /// `.*`, every value of a map. A key named `*` is written as `."*"`.
+    KeyWildcard,
+    /// `[*]`, every item of a list.
+    IndexWildcard,
+}
+
+impl AccessorKey {
+    /// Whether the key can select more than one value.
+    pub fn is_multi_valued(&self) -> bool {
+        matches!(
+            self,
+            AccessorKey::Slice(_) | AccessorKey::KeyWildcard | AccessorKey::IndexWildcard
+        )
+    }
+
+    /// The key as written in a template, including its leading separator unless it's the root.
+    pub fn to_template(&self, is_root: bool) -> String {
+        let mut template = String::new();
+        let _ = printer::write_key(&mut template, self, is_root);
+        template
+    }
 }
 
 impl From<String> for AccessorKey {
@@ -72,10 +149,52 @@ impl From<usize> for AccessorKey {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+/// A range of list items like `[1:3]` or `[::2]`. Negative bounds count from the end of the list,
+/// bounds outside of the list are clamped to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Slice {
+    pub(crate) start: Option<isize>,
+    pub(crate) end: Option<isize>,
+    pub(crate) step: NonZeroUsize,
+}
+
+impl Slice {
+    pub fn new(start: Option<isize>, end: Option<isize>, step: NonZeroUsize) -> Self {
+        Slice { start, end, step }
+    }
+
+    pub fn start(&self) -> Option<isize> {
+        self.start
+    }
+
+    pub fn end(&self) -> Option<isize> {
+        self.end
+    }
+
+    pub fn step(&self) -> NonZeroUsize {
+        self.step
+    }
+
+    /// The indices selected from a list with `len` items, in ascending order.
+    pub fn indices(&self, len: usize) -> impl Iterator<Item = usize> {
+        let clamp = |bound: isize| match usize::try_from(bound) {
+            Ok(bound) => bound.min(len),
+            Err(_) => len.saturating_sub(bound.unsigned_abs()),
+        };
+        let start = self.start.map_or(0, clamp);
+        let end = self.end.map_or(len, clamp);
+        (start..end).step_by(self.step.get())
+    }
+}
+
+/// A span inside the parsed input. `start` and `end` are byte offsets from the start of the input,
+/// the positions additionally contain the line and column of both ends.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct AccessorParserSpan {
     pub(crate) start: usize,
     pub(crate) end: usize,
+    pub(crate) start_position: SourcePosition,
+    pub(crate) end_position: SourcePosition,
 }
 
 impl AccessorParserSpan {
@@ -86,4 +205,30 @@ impl AccessorParserSpan {
     pub fn end(&self) -> usize {
         self.end
     }
+
+    pub fn start_position(&self) -> SourcePosition {
+        self.start_position
+    }
+
+    pub fn end_position(&self) -> SourcePosition {
+        self.end_position
+    }
+}
+
+/// A position inside the parsed input. Lines and columns start at 1, the column is counted in
+/// characters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SourcePosition {
+    pub(crate) line: u32,
+    pub(crate) column: usize,
+}
+
+impl SourcePosition {
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
 }
@@ -0,0 +1,151 @@
+use std::fmt::Display;
+
+use miette::{Diagnostic, LabeledSpan};
+
+use crate::{
+    error::{
+        AccessorParserError, AccessorParserErrorKind, AccessorValidationError,
+        AccessorValidationErrorKind, Severity, ValidationReport,
+    },
+    AccessorParserSpan,
+};
+
+impl Diagnostic for AccessorParserError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let code = match self.kind {
+            AccessorParserErrorKind::InvalidCharacter(_) => "accessor::invalid_character",
+            AccessorParserErrorKind::InvalidEscapeCharacter(_) => "accessor::invalid_escape",
+            AccessorParserErrorKind::InvalidUnicode(_) => "accessor::invalid_unicode",
+            AccessorParserErrorKind::InvalidAccessorKey => "accessor::invalid_key",
+            AccessorParserErrorKind::MissingClosingBracket => "accessor::missing_closing_bracket",
+            AccessorParserErrorKind::InvalidAccessor => "accessor::invalid_accessor",
+            AccessorParserErrorKind::NotANumber => "accessor::not_a_number",
+            AccessorParserErrorKind::NegativeZeroIndex => "accessor::negative_zero_index",
+            AccessorParserErrorKind::InvalidSlice => "accessor::invalid_slice",
+            AccessorParserErrorKind::TrailingInput => "accessor::trailing_input",
+            AccessorParserErrorKind::Unknown(_) => "accessor::unknown",
+        };
+        Some(Box::new(code))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.kind
+            .help()
+            .map(|help| Box::new(help) as Box<dyn Display>)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        Some(label(self.kind.to_string(), self.span))
+    }
+}
+
+impl Diagnostic for AccessorValidationError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let code = match self.kind {
+            AccessorValidationErrorKind::NumericIndexInMap => "accessor::numeric_index_in_map",
+            AccessorValidationErrorKind::InvalidRoot => "accessor::invalid_root",
+            AccessorValidationErrorKind::InvalidObjectRoot => "accessor::invalid_object_root",
+            AccessorValidationErrorKind::NotIndexable => "accessor::not_indexable",
+            AccessorValidationErrorKind::UnknownKey { .. } => "accessor::unknown_key",
+            AccessorValidationErrorKind::NotStringRepresentable => {
+                "accessor::not_string_representable"
+            }
+            AccessorValidationErrorKind::StringKeyInList => "accessor::string_key_in_list",
+            AccessorValidationErrorKind::IndexOutOfBounds { .. } => "accessor::index_out_of_bounds",
+            AccessorValidationErrorKind::TypeMismatch { .. } => "accessor::type_mismatch",
+            AccessorValidationErrorKind::DeprecatedField { .. } => "accessor::deprecated_field",
+            AccessorValidationErrorKind::SuspiciousInString { .. } => {
+                "accessor::suspicious_in_string"
+            }
+        };
+        Some(Box::new(code))
+    }
+
+    fn severity(&self) -> Option<miette::Severity> {
+        let severity = match self.severity() {
+            Severity::Error => miette::Severity::Error,
+            Severity::Warning => miette::Severity::Warning,
+            Severity::Info => miette::Severity::Advice,
+        };
+        Some(severity)
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.kind
+            .help()
+            .map(|help| Box::new(help) as Box<dyn Display>)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        Some(label(self.kind.to_string(), self.span))
+    }
+}
+
+impl Diagnostic for ValidationReport {
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        Some(Box::new(
+            self.entries.iter().map(|entry| entry as &dyn Diagnostic),
+        ))
+    }
+}
+
+fn label(
+    text: String,
+    span: AccessorParserSpan,
+) -> Box<dyn Iterator<Item = LabeledSpan> + 'static> {
+    let span = LabeledSpan::new_with_span(Some(text), span.start..span.end);
+    Box::new(std::iter::once(span))
+}
+
+#[cfg(test)]
+mod test {
+    use miette::{Diagnostic, NamedSource, Report};
+
+    use crate::{
+        string_interpolator::SpannedStringInterpolator,
+        validation::{FieldType, PathNode},
+        SpannedAccessor,
+    };
+
+    #[test]
+    fn should_describe_parser_error() {
+        let err = SpannedAccessor::parse("${event[abc]}").unwrap_err();
+
+        assert_eq!(
+            Some("accessor::not_a_number".to_owned()),
+            err.code().map(|code| code.to_string())
+        );
+        let labels: Vec<_> = err.labels().unwrap().collect();
+        match labels.as_slice() {
+            [label] => {
+                assert_eq!(Some("index is not a number"), label.label());
+                assert_eq!((8, 3), (label.offset(), label.len()));
+            }
+            labels => unreachable!("{:?}", labels),
+        }
+    }
+
+    #[test]
+    fn should_suggest_keys_as_help() {
+        let schema = PathNode::Node {
+            children: [(
+                "created_ms".to_owned(),
+                PathNode::KnownField(FieldType::Integer),
+            )]
+            .into(),
+        };
+        let source = "created at ${create_ms}";
+        let interpolator = SpannedStringInterpolator::parse(source).unwrap();
+        let report = schema.validate_interpolator(&interpolator);
+        let err = report.into_iter().next().unwrap();
+
+        assert_eq!(
+            Some("did you mean `created_ms`?".to_owned()),
+            err.help().map(|help| help.to_string())
+        );
+
+        let report = Report::new(err).with_source_code(NamedSource::new("template", source));
+        let labels: Vec<_> = report.labels().unwrap().collect();
+        assert_eq!((13, 9), (labels[0].offset(), labels[0].len()));
+    }
+}
@@ -1,49 +1,110 @@
+use std::{num::NonZeroUsize, ops::Range};
+
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_until},
     character::complete::anychar,
     combinator::verify,
-    error::Error,
+    error::{Error, ErrorKind},
     sequence::terminated,
-    Err,
+    Err, InputTake,
 };
 use nom_locate::LocatedSpan;
 
 use crate::{
     error::{AccessorParserError, AccessorParserErrorKind, InvalidUnicodeError},
-    AccessorKey, AccessorParserSpan, SpannedAccessor, SpannedAccessorKey,
+    AccessorKey, AccessorParserSpan, Slice, SourcePosition, SpannedAccessor, SpannedAccessorKey,
 };
 
-const RESERVED_TOKEN: &[char] = &['{', '}', '[', ']', '.', '$', '"'];
-const RESERVED_RAW_LITERAL: &[char] = &['"'];
+pub(crate) const RESERVED_TOKEN: &[char] = &['{', '}', '[', ']', '.', '$', '"'];
+pub(crate) const RESERVED_RAW_LITERAL: &[char] = &['"'];
 
 type PResult<'input, Output> = Result<(LocatedSpan<&'input str>, Output), Err<AccessorParserError>>;
 type NomError<'input> = Error<LocatedSpan<&'input str>>;
 
-pub(crate) fn take_spanned_accessor(input: LocatedSpan<&str>) -> PResult<SpannedAccessor> {
+pub(crate) fn parse_spanned_accessor(input: &str) -> Result<SpannedAccessor, AccessorParserError> {
+    let (rest, accessor) =
+        take_spanned_accessor(input.into()).map_err(|err| into_parser_error(err, input))?;
+
+    if !rest.is_empty() {
+        return Err(AccessorParserError {
+            kind: AccessorParserErrorKind::TrailingInput,
+            span: span_of(rest),
+        });
+    }
+
+    Ok(accessor)
+}
+
+pub(crate) fn into_parser_error(err: Err<AccessorParserError>, input: &str) -> AccessorParserError {
+    match err {
+        Err::Error(err) | Err::Failure(err) => err,
+        // All parsers operate on complete input, so this should never be reached.
+        Err::Incomplete(_) => {
+            let input = LocatedSpan::new(input);
+            let (end, _) = input.take_split(input.len());
+            AccessorParserError {
+                kind: AccessorParserErrorKind::Unknown(ErrorKind::Complete),
+                span: span_between(end, end),
+            }
+        }
+    }
+}
+
+/// The span from the start of `start` up to the start of `end`.
+pub(crate) fn span_between(start: LocatedSpan<&str>, end: LocatedSpan<&str>) -> AccessorParserSpan {
+    AccessorParserSpan {
+        start: start.location_offset(),
+        end: end.location_offset(),
+        start_position: position_of(start),
+        end_position: position_of(end),
+    }
+}
+
+/// The span covering the bytes in `range` of the input.
+pub(crate) fn span_of_range(input: &str, range: Range<usize>) -> AccessorParserSpan {
+    let input = LocatedSpan::new(input);
+    let (start, _) = input.take_split(range.start);
+    let (end, _) = input.take_split(range.end);
+    span_between(start, end)
+}
+
+/// The span covering the whole fragment.
+fn span_of(fragment: LocatedSpan<&str>) -> AccessorParserSpan {
+    let (end, _) = fragment.take_split(fragment.len());
+    span_between(fragment, end)
+}
+
+/// The span covering the first `char_count` characters of the input.
+pub(crate) fn span_of_chars(input: LocatedSpan<&str>, char_count: usize) -> AccessorParserSpan {
+    let byte_length = input
+        .fragment()
+        .char_indices()
+        .nth(char_count)
+        .map_or(input.len(), |(idx, _)| idx);
+    let (end, _) = input.take_split(byte_length);
+    span_between(input, end)
+}
+
+fn position_of(input: LocatedSpan<&str>) -> SourcePosition {
+    SourcePosition {
+        line: input.location_line(),
+        column: input.get_utf8_column(),
+    }
+}
+
+pub(crate) fn take_spanned_accessor(input: LocatedSpan<&str>) -> PResult<'_, SpannedAccessor> {
     let Ok((input, opening)) = tag::<_, _, NomError>("${")(input) else {
-        let span_start = input.get_utf8_column() - 1;
         return Err(Err::Failure(AccessorParserError {
             kind: AccessorParserErrorKind::InvalidAccessorKey,
-            span: AccessorParserSpan {
-                start: span_start,
-                end: span_start + 1,
-            },
+            span: span_of_chars(input, 1),
         }));
     };
 
     let (rest, root) = take_string_with_escape_until(is_separator, RESERVED_TOKEN)(input)?;
-    let root = {
-        let span_start = input.get_utf8_column() - 1;
-        let span_length_bytes = input.len() - rest.len();
-        let span_end = span_start + input[..span_length_bytes].chars().count();
-        SpannedAccessorKey {
-            key: root.into(),
-            span: AccessorParserSpan {
-                start: span_start,
-                end: span_end,
-            },
-        }
+    let root = SpannedAccessorKey {
+        key: root.into(),
+        span: span_between(input, rest),
     };
 
     let mut keys = vec![root];
@@ -61,105 +122,128 @@ pub(crate) fn take_spanned_accessor(input: LocatedSpan<&str>) -> PResult<Spanned
     }
 
     let Ok((input, _)) = tag::<_, _, NomError>("}")(input) else {
-        let span_start = opening.get_utf8_column() - 1;
         return Err(Err::Failure(AccessorParserError {
             kind: AccessorParserErrorKind::MissingClosingBracket,
-            span: AccessorParserSpan {
-                start: span_start,
-                end: span_start + 2,
-            },
+            span: span_of(opening),
         }));
     };
 
-    let span_start = opening.get_utf8_column() - 1;
-    let span_end = input.get_utf8_column() - 1;
-
     Ok((
         input,
         SpannedAccessor {
             keys: keys.into_boxed_slice(),
-            span: AccessorParserSpan {
-                start: span_start,
-                end: span_end,
-            },
+            span: span_between(opening, input),
         },
     ))
 }
 
-fn take_spanned_key(input: LocatedSpan<&str>) -> PResult<SpannedAccessorKey> {
+fn take_spanned_key(input: LocatedSpan<&str>) -> PResult<'_, SpannedAccessorKey> {
     let (rest, key) = take_key(input)?;
-    let span_start = input.get_utf8_column() - 1;
-    let span_byte_length = input.len() - rest.len();
-    let span_end = span_start + input[..span_byte_length].chars().count();
     Ok((
         rest,
         SpannedAccessorKey {
             key,
-            span: AccessorParserSpan {
-                start: span_start,
-                end: span_end,
-            },
+            span: span_between(input, rest),
         },
     ))
 }
 
-fn take_key(input: LocatedSpan<&str>) -> PResult<AccessorKey> {
+fn take_key(input: LocatedSpan<&str>) -> PResult<'_, AccessorKey> {
     alt((take_string_key, take_numeric_key))(input)
 }
 
-fn take_numeric_key(input: LocatedSpan<&str>) -> PResult<AccessorKey> {
+pub(crate) fn take_numeric_key(input: LocatedSpan<&str>) -> PResult<'_, AccessorKey> {
     let Ok((input, opening_bracket)) = tag::<_, _, NomError>("[")(input) else {
-        let span_start = input.get_utf8_column() - 1;
-        let next_separator = find_next_separator(input);
-        let span_end = input.fragment()[..next_separator].chars().count();
-
+        let (_, key) = input.take_split(find_next_separator(input));
         return Err(Err::Error(AccessorParserError {
             kind: AccessorParserErrorKind::InvalidAccessorKey,
-            span: AccessorParserSpan {
-                start: span_start,
-                end: span_end,
-            },
+            span: span_of(key),
         }));
     };
 
     let Ok((input, index)) = terminated(take_until("]"), tag::<_, _, NomError>("]"))(input) else {
-        let span_start = opening_bracket.get_utf8_column() - 1;
         return Err(Err::Failure(AccessorParserError {
             kind: AccessorParserErrorKind::MissingClosingBracket,
-            span: AccessorParserSpan {
-                start: span_start,
-                end: span_start + 1,
-            },
+            span: span_of(opening_bracket),
         }));
     };
 
-    let Some(index): Option<usize> = index.parse().ok() else {
-        let span_start = index.get_utf8_column() - 1;
-        let span_end = span_start + index.chars().count();
+    if *index.fragment() == "*" {
+        return Ok((input, AccessorKey::IndexWildcard));
+    }
+    if index.fragment().contains(':') {
+        return Ok((input, AccessorKey::Slice(parse_slice(index)?)));
+    }
+
+    let (negative, digits) = match index.fragment().strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, *index.fragment()),
+    };
+    let Some(value): Option<usize> = digits.parse().ok() else {
         return Err(Err::Failure(AccessorParserError {
             kind: AccessorParserErrorKind::NotANumber,
-            span: AccessorParserSpan {
-                start: span_start,
-                end: span_end,
-            },
+            span: span_of(index),
         }));
     };
 
-    Ok((input, index.into()))
+    let key = match (negative, value) {
+        (false, value) => AccessorKey::Numeric(value),
+        (true, 0) => {
+            return Err(Err::Failure(AccessorParserError {
+                kind: AccessorParserErrorKind::NegativeZeroIndex,
+                span: span_of(index),
+            }));
+        }
+        (true, value) => AccessorKey::NegativeIndex(value),
+    };
+    Ok((input, key))
 }
 
-fn take_string_key(input: LocatedSpan<&str>) -> PResult<AccessorKey> {
-    let Ok((input, _)) = tag::<_, _, NomError>(".")(input) else {
-        let span_start = input.get_utf8_column() - 1;
-        let next_separator = find_next_separator(input);
-        let span_end = input.fragment()[..next_separator].chars().count();
+/// Parses the content of a slice like `1:-1:2`, where every part is optional.
+fn parse_slice(index: LocatedSpan<&str>) -> Result<Slice, Err<AccessorParserError>> {
+    let invalid = |part: LocatedSpan<&str>| {
+        Err::Failure(AccessorParserError {
+            kind: AccessorParserErrorKind::InvalidSlice,
+            span: span_of(part),
+        })
+    };
+
+    let mut parts = vec![];
+    let mut rest = index;
+    while let Some(colon) = rest.fragment().find(':') {
+        let (after, part) = rest.take_split(colon);
+        parts.push(part);
+        rest = after.take_split(1).0;
+    }
+    parts.push(rest);
+
+    let (start, end, step) = match parts.as_slice() {
+        [start, end] => (*start, *end, None),
+        [start, end, step] => (*start, *end, Some(*step)),
+        _ => return Err(invalid(index)),
+    };
+    let bound = |part: LocatedSpan<&str>| match *part.fragment() {
+        "" => Ok(None),
+        bound => bound.parse().map(Some).map_err(|_| invalid(part)),
+    };
+    let step = match step {
+        Some(step) if !step.is_empty() => step.parse().map_err(|_| invalid(step))?,
+        _ => NonZeroUsize::MIN,
+    };
 
+    Ok(Slice {
+        start: bound(start)?,
+        end: bound(end)?,
+        step,
+    })
+}
+
+fn take_string_key(input: LocatedSpan<&str>) -> PResult<'_, AccessorKey> {
+    let Ok((input, _)) = tag::<_, _, NomError>(".")(input) else {
+        let (_, key) = input.take_split(find_next_separator(input));
         return Err(Err::Error(AccessorParserError {
             kind: AccessorParserErrorKind::InvalidAccessorKey,
-            span: AccessorParserSpan {
-                start: span_start,
-                end: span_end,
-            },
+            span: span_of(key),
         }));
     };
 
@@ -170,7 +254,13 @@ fn take_string_key(input: LocatedSpan<&str>) -> PResult<AccessorKey> {
             tag("\""),
         )(input)?
     } else {
-        take_string_with_escape_until(is_separator, RESERVED_TOKEN)(input)?
+        let (rest, key) = take_string_with_escape_until(is_separator, RESERVED_TOKEN)(input)?;
+        // Only a literal `*` is a wildcard, an escaped or quoted one is a regular key.
+        let raw_key = &input.fragment()[..rest.location_offset() - input.location_offset()];
+        if raw_key == "*" {
+            return Ok((rest, AccessorKey::KeyWildcard));
+        }
+        (rest, key)
     };
 
     Ok((input, key.into()))
@@ -190,7 +280,7 @@ fn is_separator(c: char) -> bool {
 pub(crate) fn take_string_with_escape_until<'token, Cond: Fn(char) -> bool + Copy + 'token>(
     cond: Cond,
     reserved_token: &'token [char],
-) -> impl Fn(LocatedSpan<&str>) -> PResult<String> + 'token {
+) -> impl Fn(LocatedSpan<&str>) -> PResult<'_, String> + 'token {
     move |mut input| {
         let mut buf = String::new();
         loop {
@@ -207,7 +297,9 @@ pub(crate) fn take_string_with_escape_until<'token, Cond: Fn(char) -> bool + Cop
     }
 }
 
-fn take_escaped_char(reserved_token: &[char]) -> impl Fn(LocatedSpan<&str>) -> PResult<char> + '_ {
+fn take_escaped_char(
+    reserved_token: &[char],
+) -> impl Fn(LocatedSpan<&str>) -> PResult<'_, char> + '_ {
     move |input| {
         let (input, first) = tag("\\")(input)?;
         let (rest, ch) = anychar(input)?;
@@ -218,63 +310,36 @@ fn take_escaped_char(reserved_token: &[char]) -> impl Fn(LocatedSpan<&str>) -> P
             'r' => Ok((rest, '\r')),
             '\\' => Ok((rest, '\\')),
             ch if reserved_token.contains(&ch) => Ok((rest, ch)),
-            _ => {
-                let span_start = first.get_utf8_column() - 1;
-                let span_end = rest.get_utf8_column() - 1;
-                Err(Err::Failure(AccessorParserError {
-                    kind: AccessorParserErrorKind::InvalidEscapeCharacter(ch),
-                    span: AccessorParserSpan {
-                        start: span_start,
-                        end: span_end,
-                    },
-                }))
-            }
+            _ => Err(Err::Failure(AccessorParserError {
+                kind: AccessorParserErrorKind::InvalidEscapeCharacter(ch),
+                span: span_between(first, rest),
+            })),
         }
     }
 }
 
-fn take_unicode(input: LocatedSpan<&str>) -> PResult<char> {
+fn take_unicode(input: LocatedSpan<&str>) -> PResult<'_, char> {
     let Ok((input, _)) = tag::<_, _, NomError>("{")(input) else {
-        let span_start = input.get_utf8_column() - 1;
         return Err(Err::Failure(AccessorParserError {
             kind: AccessorParserErrorKind::InvalidUnicode(
                 InvalidUnicodeError::MissingOpeningBracket,
             ),
-            span: AccessorParserSpan {
-                start: span_start,
-                end: span_start + 1,
-            },
+            span: span_of_chars(input, 1),
         }));
     };
 
     let Ok((input, unicode_code_point)) =
         terminated(take_until::<_, _, NomError>("}"), tag("}"))(input)
     else {
-        let span_start = input.get_utf8_column() - 1;
-        let span_length = input.fragment().chars().count();
-        let span_end = span_start + span_length;
-
         return Err(Err::Failure(AccessorParserError {
             kind: AccessorParserErrorKind::InvalidUnicode(
                 InvalidUnicodeError::MissingClosingBracket,
             ),
-            span: AccessorParserSpan {
-                start: span_start,
-                end: span_end,
-            },
+            span: span_of(input),
         }));
     };
 
-    let code_point_error_span = {
-        let span_start = unicode_code_point.get_utf8_column() - 1;
-        let span_length = unicode_code_point.fragment().chars().count();
-        let span_end = span_start + span_length;
-
-        AccessorParserSpan {
-            start: span_start,
-            end: span_end,
-        }
-    };
+    let code_point_error_span = span_of(unicode_code_point);
 
     if unicode_code_point.len() < 2 || unicode_code_point.len() > 8 {
         return Err(Err::Failure(AccessorParserError {
@@ -300,16 +365,13 @@ fn take_unicode(input: LocatedSpan<&str>) -> PResult<char> {
     Ok((input, ch))
 }
 
-fn take_char(reserved_token: &[char]) -> impl Fn(LocatedSpan<&str>) -> PResult<char> + '_ {
+fn take_char(reserved_token: &[char]) -> impl Fn(LocatedSpan<&str>) -> PResult<'_, char> + '_ {
     move |input| {
         let (rest, ch) = anychar(input)?;
         if reserved_token.contains(&ch) {
             return Err(Err::Failure(AccessorParserError {
                 kind: AccessorParserErrorKind::InvalidCharacter(ch),
-                span: AccessorParserSpan {
-                    start: input.get_utf8_column() - 1,
-                    end: input.get_utf8_column() - 1 + 1,
-                },
+                span: span_between(input, rest),
             }));
         }
 
@@ -324,7 +386,7 @@ mod tests {
     use crate::{
         error::{AccessorParserError, AccessorParserErrorKind, InvalidUnicodeError},
         parser::SpannedAccessorKey,
-        AccessorParserSpan,
+        Accessor, AccessorParserSpan, SpannedAccessor,
     };
 
     use super::{
@@ -347,7 +409,10 @@ mod tests {
         match err {
             nom::Err::Failure(AccessorParserError {
                 kind: AccessorParserErrorKind::InvalidCharacter('.'),
-                span: AccessorParserSpan { start: 0, end: 1 },
+                span:
+                    AccessorParserSpan {
+                        start: 0, end: 1, ..
+                    },
             }) => {}
             err => unreachable!("{:?}", err),
         }
@@ -371,7 +436,10 @@ mod tests {
         match err {
             nom::Err::Failure(AccessorParserError {
                 kind: AccessorParserErrorKind::InvalidCharacter('.'),
-                span: AccessorParserSpan { start: 1, end: 2 },
+                span:
+                    AccessorParserSpan {
+                        start: 1, end: 2, ..
+                    },
             }) => {}
             err => unreachable!("{:?}", err),
         }
@@ -392,7 +460,10 @@ mod tests {
             nom::Err::Failure(AccessorParserError {
                 kind:
                     AccessorParserErrorKind::InvalidUnicode(InvalidUnicodeError::InvalidCodeLength),
-                span: AccessorParserSpan { start: 1, end: 2 },
+                span:
+                    AccessorParserSpan {
+                        start: 1, end: 2, ..
+                    },
             }) => {}
             err => unreachable!("{:?}", err),
         }
@@ -402,7 +473,10 @@ mod tests {
             nom::Err::Failure(AccessorParserError {
                 kind:
                     AccessorParserErrorKind::InvalidUnicode(InvalidUnicodeError::InvalidCodeLength),
-                span: AccessorParserSpan { start: 1, end: 10 },
+                span:
+                    AccessorParserSpan {
+                        start: 1, end: 10, ..
+                    },
             }) => {}
             err => unreachable!("{:?}", err),
         }
@@ -415,7 +489,10 @@ mod tests {
             nom::Err::Failure(AccessorParserError {
                 kind:
                     AccessorParserErrorKind::InvalidUnicode(InvalidUnicodeError::MissingOpeningBracket),
-                span: AccessorParserSpan { start: 0, end: 1 },
+                span:
+                    AccessorParserSpan {
+                        start: 0, end: 1, ..
+                    },
             }) => {}
             err => unreachable!("{:?}", err),
         }
@@ -428,7 +505,10 @@ mod tests {
             nom::Err::Failure(AccessorParserError {
                 kind:
                     AccessorParserErrorKind::InvalidUnicode(InvalidUnicodeError::MissingClosingBracket),
-                span: AccessorParserSpan { start: 1, end: 5 },
+                span:
+                    AccessorParserSpan {
+                        start: 1, end: 5, ..
+                    },
             }) => {}
             err => unreachable!("{:?}", err),
         }
@@ -441,7 +521,10 @@ mod tests {
             nom::Err::Failure(AccessorParserError {
                 kind:
                     AccessorParserErrorKind::InvalidUnicode(InvalidUnicodeError::InvalidHexadecimal),
-                span: AccessorParserSpan { start: 1, end: 3 },
+                span:
+                    AccessorParserSpan {
+                        start: 1, end: 3, ..
+                    },
             }) => {}
             err => unreachable!("{:?}", err),
         }
@@ -453,7 +536,10 @@ mod tests {
         match err {
             nom::Err::Failure(AccessorParserError {
                 kind: AccessorParserErrorKind::InvalidUnicode(InvalidUnicodeError::InvalidCodePoint),
-                span: AccessorParserSpan { start: 1, end: 9 },
+                span:
+                    AccessorParserSpan {
+                        start: 1, end: 9, ..
+                    },
             }) => {}
             err => unreachable!("{:?}", err),
         }
@@ -483,7 +569,10 @@ mod tests {
         match err {
             nom::Err::Failure(AccessorParserError {
                 kind: AccessorParserErrorKind::InvalidEscapeCharacter('a'),
-                span: AccessorParserSpan { start: 0, end: 2 },
+                span:
+                    AccessorParserSpan {
+                        start: 0, end: 2, ..
+                    },
             }) => {}
             err => unreachable!("{:?}", err),
         }
@@ -513,7 +602,10 @@ mod tests {
         match err {
             nom::Err::Failure(AccessorParserError {
                 kind: AccessorParserErrorKind::InvalidCharacter('.'),
-                span: AccessorParserSpan { start: 2, end: 3 },
+                span:
+                    AccessorParserSpan {
+                        start: 2, end: 3, ..
+                    },
             }) => {}
             err => unreachable!("{:?}", err),
         }
@@ -525,7 +617,10 @@ mod tests {
         match err {
             nom::Err::Failure(AccessorParserError {
                 kind: AccessorParserErrorKind::InvalidEscapeCharacter('c'),
-                span: AccessorParserSpan { start: 2, end: 4 },
+                span:
+                    AccessorParserSpan {
+                        start: 2, end: 4, ..
+                    },
             }) => {}
             err => unreachable!("{:?}", err),
         }
@@ -583,6 +678,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn should_take_wildcards() {
+        let (rest, key) = take_string_key(".*.key".into()).unwrap();
+        assert_eq!(".key", *rest.fragment());
+        match key {
+            AccessorKey::KeyWildcard => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        let (rest, key) = take_numeric_key("[*].key".into()).unwrap();
+        assert_eq!(".key", *rest.fragment());
+        match key {
+            AccessorKey::IndexWildcard => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        for input in [".\"*\"", ".\\u{2a}", ".**"] {
+            match take_string_key(input.into()).unwrap().1 {
+                AccessorKey::String(_) => {}
+                err => unreachable!("{:?}", err),
+            }
+        }
+    }
+
     #[test]
     fn should_take_last_string_key() {
         let (rest, key) = take_string_key(".key}".into()).unwrap();
@@ -600,7 +719,10 @@ mod tests {
         match err {
             nom::Err::Error(AccessorParserError {
                 kind: AccessorParserErrorKind::InvalidAccessorKey,
-                span: AccessorParserSpan { start: 0, end: 3 },
+                span:
+                    AccessorParserSpan {
+                        start: 0, end: 3, ..
+                    },
             }) => {}
             err => unreachable!("{:?}", err),
         }
@@ -612,7 +734,10 @@ mod tests {
         match err {
             nom::Err::Error(AccessorParserError {
                 kind: AccessorParserErrorKind::InvalidAccessorKey,
-                span: AccessorParserSpan { start: 0, end: 3 },
+                span:
+                    AccessorParserSpan {
+                        start: 0, end: 3, ..
+                    },
             }) => {}
             err => unreachable!("{:?}", err),
         }
@@ -621,7 +746,10 @@ mod tests {
         match err {
             nom::Err::Error(AccessorParserError {
                 kind: AccessorParserErrorKind::InvalidAccessorKey,
-                span: AccessorParserSpan { start: 0, end: 3 },
+                span:
+                    AccessorParserSpan {
+                        start: 0, end: 3, ..
+                    },
             }) => {}
             err => unreachable!("{:?}", err),
         }
@@ -630,7 +758,10 @@ mod tests {
         match err {
             nom::Err::Error(AccessorParserError {
                 kind: AccessorParserErrorKind::InvalidAccessorKey,
-                span: AccessorParserSpan { start: 0, end: 3 },
+                span:
+                    AccessorParserSpan {
+                        start: 0, end: 3, ..
+                    },
             }) => {}
             err => unreachable!("{:?}", err),
         }
@@ -664,7 +795,10 @@ mod tests {
         match err {
             nom::Err::Error(AccessorParserError {
                 kind: AccessorParserErrorKind::InvalidAccessorKey,
-                span: AccessorParserSpan { start: 0, end: 5 },
+                span:
+                    AccessorParserSpan {
+                        start: 0, end: 5, ..
+                    },
             }) => {}
             err => unreachable!("{:?}", err),
         }
@@ -676,7 +810,97 @@ mod tests {
         match err {
             nom::Err::Failure(AccessorParserError {
                 kind: AccessorParserErrorKind::MissingClosingBracket,
-                span: AccessorParserSpan { start: 0, end: 1 },
+                span:
+                    AccessorParserSpan {
+                        start: 0, end: 1, ..
+                    },
+            }) => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_take_negative_index() {
+        let (rest, key) = take_numeric_key("[-12].key".into()).unwrap();
+        assert_eq!(".key", *rest.fragment());
+        assert_eq!(5, rest.get_utf8_column() - 1);
+        match key {
+            AccessorKey::NegativeIndex(12) => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_take_negative_zero_index() {
+        let err = take_numeric_key("[-0]".into()).unwrap_err();
+        match err {
+            nom::Err::Failure(AccessorParserError {
+                kind: AccessorParserErrorKind::NegativeZeroIndex,
+                span:
+                    AccessorParserSpan {
+                        start: 1, end: 3, ..
+                    },
+            }) => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        let err = take_numeric_key("[--1]".into()).unwrap_err();
+        match err {
+            nom::Err::Failure(AccessorParserError {
+                kind: AccessorParserErrorKind::NotANumber,
+                span:
+                    AccessorParserSpan {
+                        start: 1, end: 4, ..
+                    },
+            }) => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_take_slice() {
+        let (rest, key) = take_numeric_key("[1:-1:2].key".into()).unwrap();
+        assert_eq!(".key", *rest.fragment());
+        assert_eq!(8, rest.get_utf8_column() - 1);
+        match key {
+            AccessorKey::Slice(slice) => {
+                assert_eq!(Some(1), slice.start());
+                assert_eq!(Some(-1), slice.end());
+                assert_eq!(2, slice.step().get());
+            }
+            err => unreachable!("{:?}", err),
+        }
+
+        let (_, key) = take_numeric_key("[:]".into()).unwrap();
+        match key {
+            AccessorKey::Slice(slice) => {
+                assert_eq!(None, slice.start());
+                assert_eq!(None, slice.end());
+                assert_eq!(1, slice.step().get());
+            }
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_take_invalid_slice() {
+        for (input, start, end) in [("[1:2:0]", 5, 6), ("[a:]", 1, 2), ("[::-1]", 3, 5)] {
+            match take_numeric_key(input.into()).unwrap_err() {
+                nom::Err::Failure(AccessorParserError {
+                    kind: AccessorParserErrorKind::InvalidSlice,
+                    span,
+                }) => assert_eq!((start, end), (span.start, span.end), "{input}"),
+                err => unreachable!("{:?}", err),
+            }
+        }
+
+        match take_numeric_key("[1:2:3:4]".into()).unwrap_err() {
+            nom::Err::Failure(AccessorParserError {
+                kind: AccessorParserErrorKind::InvalidSlice,
+                span:
+                    AccessorParserSpan {
+                        start: 1, end: 8, ..
+                    },
             }) => {}
             err => unreachable!("{:?}", err),
         }
@@ -688,7 +912,10 @@ mod tests {
         match err {
             nom::Err::Failure(AccessorParserError {
                 kind: AccessorParserErrorKind::NotANumber,
-                span: AccessorParserSpan { start: 1, end: 4 },
+                span:
+                    AccessorParserSpan {
+                        start: 1, end: 4, ..
+                    },
             }) => {}
             err => unreachable!("{:?}", err),
         }
@@ -723,7 +950,10 @@ mod tests {
         match key {
             SpannedAccessorKey {
                 key: AccessorKey::String(key),
-                span: AccessorParserSpan { start: 0, end: 4 },
+                span:
+                    AccessorParserSpan {
+                        start: 0, end: 4, ..
+                    },
             } if key.as_ref() == "key" => {}
             err => unreachable!("{:?}", err),
         }
@@ -737,13 +967,22 @@ mod tests {
         match key.as_slice() {
             [SpannedAccessorKey {
                 key: AccessorKey::String(key1),
-                span: AccessorParserSpan { start: 0, end: 5 },
+                span:
+                    AccessorParserSpan {
+                        start: 0, end: 5, ..
+                    },
             }, SpannedAccessorKey {
                 key: AccessorKey::Numeric(1234),
-                span: AccessorParserSpan { start: 5, end: 11 },
+                span:
+                    AccessorParserSpan {
+                        start: 5, end: 11, ..
+                    },
             }, SpannedAccessorKey {
                 key: AccessorKey::String(key2),
-                span: AccessorParserSpan { start: 11, end: 16 },
+                span:
+                    AccessorParserSpan {
+                        start: 11, end: 16, ..
+                    },
             }] if key1.as_ref() == "key1" && key2.as_ref() == "key2" => {}
             err => unreachable!("{:?}", err),
         }
@@ -759,7 +998,10 @@ mod tests {
         match accessor.keys.as_ref() {
             [SpannedAccessorKey {
                 key: AccessorKey::String(key),
-                span: AccessorParserSpan { start: 2, end: 5 },
+                span:
+                    AccessorParserSpan {
+                        start: 2, end: 5, ..
+                    },
             }] if key.as_ref() == "key" => {}
             err => unreachable!("{:?}", err),
         }
@@ -774,13 +1016,22 @@ mod tests {
         match accessor.keys.as_ref() {
             [SpannedAccessorKey {
                 key: AccessorKey::String(key1),
-                span: AccessorParserSpan { start: 2, end: 6 },
+                span:
+                    AccessorParserSpan {
+                        start: 2, end: 6, ..
+                    },
             }, SpannedAccessorKey {
                 key: AccessorKey::Numeric(1234),
-                span: AccessorParserSpan { start: 6, end: 12 },
+                span:
+                    AccessorParserSpan {
+                        start: 6, end: 12, ..
+                    },
             }, SpannedAccessorKey {
                 key: AccessorKey::String(key2),
-                span: AccessorParserSpan { start: 12, end: 17 },
+                span:
+                    AccessorParserSpan {
+                        start: 12, end: 17, ..
+                    },
             }] if key1.as_ref() == "key1" && key2.as_ref() == "key2" => {}
             err => unreachable!("{:?}", err),
         }
@@ -792,9 +1043,70 @@ mod tests {
         match err {
             nom::Err::Failure(AccessorParserError {
                 kind: AccessorParserErrorKind::MissingClosingBracket,
-                span: AccessorParserSpan { start: 0, end: 2 },
+                span:
+                    AccessorParserSpan {
+                        start: 0, end: 2, ..
+                    },
             }) => {}
             err => unreachable!("{:?}", err),
         }
     }
+
+    #[test]
+    fn should_parse_complete_accessor() {
+        let accessor = SpannedAccessor::parse("${event.created_ms}").unwrap();
+        assert_eq!((0, 19), (accessor.span.start, accessor.span.end));
+        match accessor.keys.as_ref() {
+            [SpannedAccessorKey {
+                key: AccessorKey::String(key1),
+                span:
+                    AccessorParserSpan {
+                        start: 2, end: 7, ..
+                    },
+            }, SpannedAccessorKey {
+                key: AccessorKey::String(key2),
+                span:
+                    AccessorParserSpan {
+                        start: 7, end: 18, ..
+                    },
+            }] if key1.as_ref() == "event" && key2.as_ref() == "created_ms" => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        let accessor: Accessor = "${key1[1234]}".parse().unwrap();
+        match accessor.keys() {
+            [AccessorKey::String(key1), AccessorKey::Numeric(1234)] if key1.as_ref() == "key1" => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_parse_accessor_with_trailing_input() {
+        let err = SpannedAccessor::parse("${key} -").unwrap_err();
+        match err {
+            AccessorParserError {
+                kind: AccessorParserErrorKind::TrailingInput,
+                span:
+                    AccessorParserSpan {
+                        start: 6, end: 8, ..
+                    },
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_parse_invalid_accessor() {
+        let err = "${key1[abc]}".parse::<Accessor>().unwrap_err();
+        match err {
+            AccessorParserError {
+                kind: AccessorParserErrorKind::NotANumber,
+                span:
+                    AccessorParserSpan {
+                        start: 7, end: 10, ..
+                    },
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
 }
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+
+use crate::{validation::PathNode, Accessor, AccessorKey};
+
+/// A path allowed by a [`PathNode`] tree, see [`PathNode::paths`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaPath<'a> {
+    accessor: Accessor,
+    node: &'a PathNode,
+}
+
+impl<'a> SchemaPath<'a> {
+    pub fn accessor(&self) -> &Accessor {
+        &self.accessor
+    }
+
+    /// The node reached by the path.
+    pub fn node(&self) -> &'a PathNode {
+        self.node
+    }
+
+    /// Whether the path can be continued with keys, that aren't listed by the iterator.
+    pub fn is_open(&self) -> bool {
+        matches!(
+            self.node.without_deprecation(),
+            PathNode::Root | PathNode::ObjectRoot | PathNode::List { .. } | PathNode::Map { .. }
+        )
+    }
+}
+
+/// Iterates depth first over all paths of a [`PathNode`] tree, with the keys of each node in
+/// alphabetical order.
+#[derive(Debug, Clone)]
+pub struct Paths<'a> {
+    stack: Vec<(Vec<AccessorKey>, &'a PathNode)>,
+    max_depth: Option<usize>,
+}
+
+impl PathNode {
+    /// All paths allowed by the tree, except the empty one.
+    ///
+    /// Lists and maps accept any index or key, so the iterator marks them as
+    /// [open](SchemaPath::is_open), like [`PathNode::Root`] and [`PathNode::ObjectRoot`], and
+    /// continues with their items as `[*]` and their values as `.*`.
+    pub fn paths(&self) -> Paths<'_> {
+        let mut paths = Paths {
+            stack: vec![],
+            max_depth: None,
+        };
+        paths.push_children(&[], self);
+        paths
+    }
+
+    /// The children of a [`PathNode::Node`], also when it's deprecated.
+    pub fn children(&self) -> Option<&HashMap<String, PathNode>> {
+        match self.without_deprecation() {
+            PathNode::Node { children } => Some(children),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Paths<'a> {
+    /// Skips all paths with more than `max_depth` keys.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self.stack.retain(|(keys, _)| keys.len() <= max_depth);
+        self
+    }
+
+    fn push_children(&mut self, keys: &[AccessorKey], node: &'a PathNode) {
+        if self
+            .max_depth
+            .is_some_and(|max_depth| keys.len() >= max_depth)
+        {
+            return;
+        }
+
+        let children = match node.without_deprecation() {
+            PathNode::Node { children } => children,
+            PathNode::List { item, .. } => {
+                return self.push_child(keys, AccessorKey::IndexWildcard, item)
+            }
+            PathNode::Map { value } => {
+                return self.push_child(keys, AccessorKey::KeyWildcard, value)
+            }
+            _ => return,
+        };
+
+        let mut children: Vec<_> = children.iter().collect();
+        // The stack is popped from the back, so the children are pushed in reverse order.
+        children.sort_by(|(a, _), (b, _)| b.cmp(a));
+        for (key, child) in children {
+            self.push_child(keys, AccessorKey::String(key.as_str().into()), child);
+        }
+    }
+
+    fn push_child(&mut self, keys: &[AccessorKey], key: AccessorKey, child: &'a PathNode) {
+        let mut keys = keys.to_vec();
+        keys.push(key);
+        self.stack.push((keys, child));
+    }
+}
+
+impl<'a> Iterator for Paths<'a> {
+    type Item = SchemaPath<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (keys, node) = self.stack.pop()?;
+        self.push_children(&keys, node);
+
+        Some(SchemaPath {
+            accessor: Accessor { keys: keys.into() },
+            node,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::validation::{FieldType, PathNode};
+
+    fn test_path_tree() -> PathNode {
+        crate::path_node! {
+            "event" => {
+                "created_ms" => integer,
+                "metadata" => object_root,
+                "tags" => [{ "name" => string }],
+                "source" => deprecated({ "host" => string }),
+            },
+            "item" => root,
+            "sources" => map({ "host" => string }),
+        }
+        .unwrap()
+    }
+
+    #[test]
+    fn should_list_all_paths() {
+        let tree = test_path_tree();
+        let paths: Vec<_> = tree
+            .paths()
+            .map(|path| (path.accessor().to_string(), path.is_open()))
+            .collect();
+
+        assert_eq!(
+            vec![
+                ("${event}".to_owned(), false),
+                ("${event.created_ms}".to_owned(), false),
+                ("${event.metadata}".to_owned(), true),
+                ("${event.source}".to_owned(), false),
+                ("${event.source.host}".to_owned(), false),
+                ("${event.tags}".to_owned(), true),
+                ("${event.tags[*]}".to_owned(), false),
+                ("${event.tags[*].name}".to_owned(), false),
+                ("${item}".to_owned(), true),
+                ("${sources}".to_owned(), true),
+                ("${sources.*}".to_owned(), false),
+                ("${sources.*.host}".to_owned(), false),
+            ],
+            paths
+        );
+
+        let path = tree.paths().nth(1).unwrap();
+        assert_eq!(&PathNode::KnownField(FieldType::Integer), path.node());
+        assert_eq!(
+            path.node(),
+            &tree.children().unwrap()["event"].children().unwrap()["created_ms"]
+        );
+    }
+
+    #[test]
+    fn should_limit_depth_of_paths() {
+        let tree = test_path_tree();
+        let paths: Vec<_> = tree
+            .paths()
+            .max_depth(1)
+            .map(|path| path.accessor().to_string())
+            .collect();
+
+        assert_eq!(vec!["${event}", "${item}", "${sources}"], paths);
+        assert_eq!(0, tree.paths().max_depth(0).count());
+    }
+}
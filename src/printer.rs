@@ -0,0 +1,190 @@
+use std::fmt::{self, Write};
+
+use crate::{
+    parser::{RESERVED_RAW_LITERAL, RESERVED_TOKEN},
+    AccessorKey,
+};
+
+const RESERVED_TEXT: &[char] = &['$'];
+
+pub(crate) fn write_accessor<'a>(
+    f: &mut fmt::Formatter<'_>,
+    keys: impl Iterator<Item = &'a AccessorKey>,
+) -> fmt::Result {
+    f.write_str("${")?;
+    for (idx, key) in keys.enumerate() {
+        write_key(f, key, idx == 0)?;
+    }
+    f.write_char('}')
+}
+
+/// Writes a single key including its leading separator, the root key has none.
+pub(crate) fn write_key(f: &mut impl Write, key: &AccessorKey, is_root: bool) -> fmt::Result {
+    match key {
+        AccessorKey::String(key) if is_root => write_escaped(f, key, RESERVED_TOKEN, true),
+        AccessorKey::String(key) if key.contains(RESERVED_TOKEN) || key.as_ref() == "*" => {
+            f.write_str(".\"")?;
+            write_escaped(f, key, RESERVED_RAW_LITERAL, true)?;
+            f.write_char('"')
+        }
+        AccessorKey::String(key) => {
+            f.write_char('.')?;
+            write_escaped(f, key, RESERVED_TOKEN, true)
+        }
+        AccessorKey::Numeric(index) => write!(f, "[{index}]"),
+        AccessorKey::NegativeIndex(index) => write!(f, "[-{index}]"),
+        AccessorKey::KeyWildcard => f.write_str(".*"),
+        AccessorKey::IndexWildcard => f.write_str("[*]"),
+        AccessorKey::Slice(slice) => {
+            f.write_char('[')?;
+            if let Some(start) = slice.start {
+                write!(f, "{start}")?;
+            }
+            f.write_char(':')?;
+            if let Some(end) = slice.end {
+                write!(f, "{end}")?;
+            }
+            if slice.step.get() != 1 {
+                write!(f, ":{}", slice.step)?;
+            }
+            f.write_char(']')
+        }
+    }
+}
+
+/// Writes the prefix and accessor of each segment, followed by the postfix.
+pub(crate) fn write_interpolator<'a, A: fmt::Display + 'a>(
+    f: &mut fmt::Formatter<'_>,
+    segments: impl Iterator<Item = (&'a str, &'a A)>,
+    postfix: &str,
+) -> fmt::Result {
+    for (prefix, accessor) in segments {
+        write_text(f, prefix)?;
+        write!(f, "{accessor}")?;
+    }
+    write_text(f, postfix)
+}
+
+pub(crate) fn write_text(f: &mut fmt::Formatter<'_>, text: &str) -> fmt::Result {
+    // Keep line breaks literal, so multi-line templates stay readable.
+    write_escaped(f, text, RESERVED_TEXT, false)
+}
+
+fn write_escaped(
+    f: &mut impl Write,
+    s: &str,
+    reserved_token: &[char],
+    escape_whitespace: bool,
+) -> fmt::Result {
+    for ch in s.chars() {
+        match ch {
+            '\\' => f.write_str("\\\\")?,
+            '\n' if escape_whitespace => f.write_str("\\n")?,
+            '\t' if escape_whitespace => f.write_str("\\t")?,
+            '\r' if escape_whitespace => f.write_str("\\r")?,
+            ch if reserved_token.contains(&ch) => write!(f, "\\u{{{:02x}}}", ch as u32)?,
+            ch => f.write_char(ch)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{string_interpolator::StringInterpolator, Accessor, AccessorKey, SpannedAccessor};
+
+    fn assert_accessor_round_trip(input: &str, canonical: &str) {
+        let accessor: Accessor = input.parse().unwrap();
+        let printed = accessor.to_string();
+        assert_eq!(canonical, printed);
+
+        let reparsed: Accessor = printed.parse().unwrap();
+        assert_eq!(accessor, reparsed);
+    }
+
+    fn assert_interpolator_round_trip(input: &str, canonical: &str) {
+        let interpolator: StringInterpolator = input.parse().unwrap();
+        let printed = interpolator.to_string();
+        assert_eq!(canonical, printed);
+
+        let reparsed: StringInterpolator = printed.parse().unwrap();
+        assert_eq!(interpolator, reparsed);
+    }
+
+    #[test]
+    fn should_round_trip_plain_keys() {
+        assert_accessor_round_trip("${item}", "${item}");
+        assert_accessor_round_trip("${event.created_ms}", "${event.created_ms}");
+        assert_accessor_round_trip("${a.b[3]}", "${a.b[3]}");
+        assert_accessor_round_trip("${a[1][2].b}", "${a[1][2].b}");
+        assert_accessor_round_trip("${a.b[-1]}", "${a.b[-1]}");
+        assert_accessor_round_trip("${a[-12][0]}", "${a[-12][0]}");
+        assert_accessor_round_trip("${a[1:3]}", "${a[1:3]}");
+        assert_accessor_round_trip("${a[::2].b}", "${a[::2].b}");
+        assert_accessor_round_trip("${a[-2:]}", "${a[-2:]}");
+        assert_accessor_round_trip("${a[:-1:1]}", "${a[:-1]}");
+        assert_accessor_round_trip("${a.*}", "${a.*}");
+        assert_accessor_round_trip("${a[*].b.*}", "${a[*].b.*}");
+    }
+
+    #[test]
+    fn should_round_trip_empty_keys() {
+        assert_accessor_round_trip("${}", "${}");
+        assert_accessor_round_trip("${a.}", "${a.}");
+        assert_accessor_round_trip("${a.\"\"}", "${a.}");
+    }
+
+    #[test]
+    fn should_round_trip_raw_string_keys() {
+        assert_accessor_round_trip("${a.\"key.with.dots\"}", "${a.\"key.with.dots\"}");
+        assert_accessor_round_trip("${a.\"key\"}", "${a.key}");
+        assert_accessor_round_trip("${a.\"*\"}", "${a.\"*\"}");
+        assert_accessor_round_trip("${a.\\u{2a}}", "${a.\"*\"}");
+        assert_accessor_round_trip("${*}", "${*}");
+        assert_accessor_round_trip("${a.\"{[$]}\"}", "${a.\"{[$]}\"}");
+        assert_accessor_round_trip("${a.\"say \\\"hi\\\"\"}", "${a.\"say \\u{22}hi\\u{22}\"}");
+    }
+
+    #[test]
+    fn should_round_trip_escaped_characters() {
+        assert_accessor_round_trip("${a\\.b}", "${a\\u{2e}b}");
+        assert_accessor_round_trip("${a\\u{7b}\\u{7d}}", "${a\\u{7b}\\u{7d}}");
+        assert_accessor_round_trip("${a.key\\\\}", "${a.key\\\\}");
+        assert_accessor_round_trip("${a.\\n\\t\\r}", "${a.\\n\\t\\r}");
+        assert_accessor_round_trip("${a.\\u{1F600}}", "${a.\u{1F600}}");
+    }
+
+    #[test]
+    fn should_round_trip_spanned_accessor() {
+        let accessor = SpannedAccessor::parse("${a.\"b\"[1]}").unwrap();
+        assert_eq!("${a.b[1]}", accessor.to_string());
+    }
+
+    #[test]
+    fn should_write_single_keys() {
+        let key = AccessorKey::from("key.with.dots".to_owned());
+        assert_eq!("key\\u{2e}with\\u{2e}dots", key.to_template(true));
+        assert_eq!(".\"key.with.dots\"", key.to_template(false));
+        assert_eq!(
+            ".name",
+            AccessorKey::from("name".to_owned()).to_template(false)
+        );
+        assert_eq!("[3]", AccessorKey::from(3).to_template(false));
+        assert_eq!("[-3]", AccessorKey::NegativeIndex(3).to_template(false));
+    }
+
+    #[test]
+    fn should_round_trip_interpolator() {
+        assert_interpolator_round_trip("", "");
+        assert_interpolator_round_trip("plain text", "plain text");
+        assert_interpolator_round_trip(
+            "${event.created_ms} - ${item}",
+            "${event.created_ms} - ${item}",
+        );
+        assert_interpolator_round_trip("- ${a.\"b.c\"[0]} -", "- ${a.\"b.c\"[0]} -");
+        assert_interpolator_round_trip("costs \\$5 ${a}", "costs \\u{24}5 ${a}");
+        assert_interpolator_round_trip("back\\\\slash {}[].\"", "back\\\\slash {}[].\"");
+        assert_interpolator_round_trip("line 1\nline 2 ${a}\n", "line 1\nline 2 ${a}\n");
+        assert_interpolator_round_trip("tab\\t${a}", "tab\t${a}");
+    }
+}
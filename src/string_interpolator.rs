@@ -1,23 +1,216 @@
+use std::{fmt, str::FromStr};
+
 use nom_locate::LocatedSpan;
 
 use crate::{
-    error::AccessorParserError,
-    parser::{take_spanned_accessor, take_string_with_escape_until},
-    Accessor, SpannedAccessor,
+    accessible::Accessible,
+    error::{AccessorParserError, EvalError, RenderError, RenderErrorKind},
+    parser::{into_parser_error, take_spanned_accessor, take_string_with_escape_until},
+    printer, Accessor, SpannedAccessor,
 };
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct SpannedStringInterpolator {
     pub(crate) segments: Vec<SpannedInterpolatorSegment>,
     postfix: Box<str>,
 }
 
-#[derive(Debug)]
+impl SpannedStringInterpolator {
+    /// Parses a template like `${event.created_ms} - ${item}`.
+    pub fn parse(input: &str) -> Result<SpannedStringInterpolator, AccessorParserError> {
+        take_spanned_interpolator(input.into()).map_err(|err| into_parser_error(err, input))
+    }
+
+    /// Renders the template like [`StringInterpolator::render`], errors carry the
+    /// [span](RenderError::span) of the failing accessor.
+    pub fn render(&self, ctx: &impl Accessible) -> Result<String, RenderError> {
+        self.render_with(ctx, &RenderOptions::default())
+    }
+
+    /// See [`StringInterpolator::render_with`].
+    pub fn render_with(
+        &self,
+        ctx: &impl Accessible,
+        options: &RenderOptions,
+    ) -> Result<String, RenderError> {
+        render_segments(&self.segments, &self.postfix, ctx, options)
+    }
+
+    pub fn segments(&self) -> &[SpannedInterpolatorSegment] {
+        &self.segments
+    }
+
+    pub fn postfix(&self) -> &str {
+        &self.postfix
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
 pub struct StringInterpolator {
     segments: Box<[InterpolatorSegment]>,
     postfix: Box<str>,
 }
 
+impl StringInterpolator {
+    /// Renders the template, by replacing every accessor with the resolved value of `ctx`.
+    /// All accessors have to resolve to a [`crate::accessible::Scalar`], see there for how each
+    /// scalar is turned into a string.
+    pub fn render(&self, ctx: &impl Accessible) -> Result<String, RenderError> {
+        self.render_with(ctx, &RenderOptions::default())
+    }
+
+    /// Renders the template like [`StringInterpolator::render`]. The values of multi-valued
+    /// accessors like `${items[1:3]}` are joined with [`RenderOptions::joiner`].
+    pub fn render_with(
+        &self,
+        ctx: &impl Accessible,
+        options: &RenderOptions,
+    ) -> Result<String, RenderError> {
+        render_segments(&self.segments, &self.postfix, ctx, options)
+    }
+
+    pub fn segments(&self) -> &[InterpolatorSegment] {
+        &self.segments
+    }
+
+    pub fn postfix(&self) -> &str {
+        &self.postfix
+    }
+}
+
+/// Options for [`StringInterpolator::render_with`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderOptions {
+    joiner: Box<str>,
+}
+
+impl RenderOptions {
+    /// The separator between the values of a multi-valued accessor, `", "` by default.
+    pub fn joiner(mut self, joiner: impl Into<Box<str>>) -> Self {
+        self.joiner = joiner.into();
+        self
+    }
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            joiner: ", ".into(),
+        }
+    }
+}
+
+/// The segments of both interpolators, so they share a single render loop.
+trait RenderSegment {
+    fn prefix(&self) -> &str;
+
+    fn resolve_all<'value>(
+        &self,
+        ctx: &'value dyn Accessible,
+    ) -> Result<Vec<&'value dyn Accessible>, EvalError>;
+
+    fn error(&self, kind: RenderErrorKind) -> RenderError;
+}
+
+impl RenderSegment for InterpolatorSegment {
+    fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    fn resolve_all<'value>(
+        &self,
+        ctx: &'value dyn Accessible,
+    ) -> Result<Vec<&'value dyn Accessible>, EvalError> {
+        self.accessor.resolve_all(ctx)
+    }
+
+    fn error(&self, kind: RenderErrorKind) -> RenderError {
+        RenderError {
+            kind,
+            accessor: self.accessor.clone(),
+            span: None,
+        }
+    }
+}
+
+impl RenderSegment for SpannedInterpolatorSegment {
+    fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    fn resolve_all<'value>(
+        &self,
+        ctx: &'value dyn Accessible,
+    ) -> Result<Vec<&'value dyn Accessible>, EvalError> {
+        self.accessor.resolve_all(ctx)
+    }
+
+    fn error(&self, kind: RenderErrorKind) -> RenderError {
+        RenderError {
+            kind,
+            accessor: self.accessor.clone().into(),
+            span: Some(Box::new(self.accessor.span())),
+        }
+    }
+}
+
+fn render_segments(
+    segments: &[impl RenderSegment],
+    postfix: &str,
+    ctx: &dyn Accessible,
+    options: &RenderOptions,
+) -> Result<String, RenderError> {
+    let mut buf = String::new();
+    for segment in segments {
+        buf.push_str(segment.prefix());
+
+        let values = segment
+            .resolve_all(ctx)
+            .map_err(|err| segment.error(RenderErrorKind::Eval(err)))?;
+        for (idx, value) in values.into_iter().enumerate() {
+            let Some(scalar) = value.as_scalar() else {
+                return Err(segment.error(RenderErrorKind::NotStringRepresentable));
+            };
+
+            if idx > 0 {
+                buf.push_str(&options.joiner);
+            }
+            buf.push_str(&scalar.to_string());
+        }
+    }
+    buf.push_str(postfix);
+
+    Ok(buf)
+}
+
+impl fmt::Display for SpannedStringInterpolator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let segments = self
+            .segments
+            .iter()
+            .map(|segment| (segment.prefix.as_ref(), &segment.accessor));
+        printer::write_interpolator(f, segments, &self.postfix)
+    }
+}
+
+impl fmt::Display for StringInterpolator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let segments = self
+            .segments
+            .iter()
+            .map(|segment| (segment.prefix.as_ref(), &segment.accessor));
+        printer::write_interpolator(f, segments, &self.postfix)
+    }
+}
+
+impl FromStr for StringInterpolator {
+    type Err = AccessorParserError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SpannedStringInterpolator::parse(s).map(Into::into)
+    }
+}
+
 impl From<SpannedStringInterpolator> for StringInterpolator {
     fn from(value: SpannedStringInterpolator) -> Self {
         StringInterpolator {
@@ -27,19 +220,38 @@ impl From<SpannedStringInterpolator> for StringInterpolator {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct SpannedInterpolatorSegment {
     prefix: Box<str>,
     pub(crate) accessor: SpannedAccessor,
 }
 
-#[derive(Debug)]
-#[allow(dead_code)]
+impl SpannedInterpolatorSegment {
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn accessor(&self) -> &SpannedAccessor {
+        &self.accessor
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
 pub struct InterpolatorSegment {
     prefix: Box<str>,
     accessor: Accessor,
 }
 
+impl InterpolatorSegment {
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn accessor(&self) -> &Accessor {
+        &self.accessor
+    }
+}
+
 impl From<SpannedInterpolatorSegment> for InterpolatorSegment {
     fn from(value: SpannedInterpolatorSegment) -> Self {
         InterpolatorSegment {
@@ -49,8 +261,15 @@ impl From<SpannedInterpolatorSegment> for InterpolatorSegment {
     }
 }
 
+#[deprecated(note = "use SpannedStringInterpolator::parse")]
 pub fn take_spanned_string_interpolator(
     input: LocatedSpan<&str>,
+) -> Result<SpannedStringInterpolator, nom::Err<AccessorParserError>> {
+    take_spanned_interpolator(input)
+}
+
+pub(crate) fn take_spanned_interpolator(
+    input: LocatedSpan<&str>,
 ) -> Result<SpannedStringInterpolator, nom::Err<AccessorParserError>> {
     let mut segments = vec![];
     let mut input = input;
@@ -75,16 +294,51 @@ pub fn take_spanned_string_interpolator(
 
 #[cfg(test)]
 mod test {
+    use std::collections::HashMap;
+
+    use maplit::hashmap;
+
     use crate::{
-        string_interpolator::take_spanned_string_interpolator, AccessorKey, AccessorParserSpan,
-        SpannedAccessor, SpannedAccessorKey,
+        accessible::{Accessible, Scalar},
+        error::{
+            AccessorParserError, AccessorParserErrorKind, EvalError, EvalErrorKind, RenderError,
+            RenderErrorKind,
+        },
+        string_interpolator::take_spanned_interpolator,
+        AccessorKey, AccessorParserSpan, SourcePosition, SpannedAccessor, SpannedAccessorKey,
+    };
+
+    use super::{
+        RenderOptions, SpannedInterpolatorSegment, SpannedStringInterpolator, StringInterpolator,
     };
 
-    use super::{SpannedInterpolatorSegment, SpannedStringInterpolator};
+    struct Event {
+        created_ms: u64,
+        tags: Vec<String>,
+    }
+
+    impl Accessible for Event {
+        fn get_key(&self, key: &str) -> Result<&dyn Accessible, EvalErrorKind> {
+            match key {
+                "created_ms" => Ok(&self.created_ms),
+                "tags" => Ok(&self.tags),
+                _ => Err(EvalErrorKind::MissingKey),
+            }
+        }
+    }
+
+    fn test_context() -> HashMap<String, Event> {
+        hashmap! {
+            "event".to_owned() => Event {
+                created_ms: 1234,
+                tags: vec!["first".to_owned()],
+            },
+        }
+    }
 
     #[test]
     fn should_take_string_interpolation_with_postfix() {
-        let interpolator = take_spanned_string_interpolator("${item} -".into()).unwrap();
+        let interpolator = take_spanned_interpolator("${item} -".into()).unwrap();
         let segments = match interpolator {
             SpannedStringInterpolator { segments, postfix } if postfix.as_ref() == " -" => segments,
             err => unreachable!("{:?}", err),
@@ -96,7 +350,10 @@ mod test {
                 accessor:
                     SpannedAccessor {
                         keys,
-                        span: AccessorParserSpan { start: 0, end: 7 },
+                        span:
+                            AccessorParserSpan {
+                                start: 0, end: 7, ..
+                            },
                     },
             }] if prefix.as_ref() == "" => keys,
             err => unreachable!("{:?}", err),
@@ -105,7 +362,10 @@ mod test {
         match keys.as_ref() {
             [SpannedAccessorKey {
                 key: AccessorKey::String(key),
-                span: AccessorParserSpan { start: 2, end: 6 },
+                span:
+                    AccessorParserSpan {
+                        start: 2, end: 6, ..
+                    },
             }] if key.as_ref() == "item" => {}
             err => unreachable!("{:?}", err),
         }
@@ -113,7 +373,7 @@ mod test {
 
     #[test]
     fn should_take_string_interpolation_with_prefix() {
-        let interpolator = take_spanned_string_interpolator("- ${item}".into()).unwrap();
+        let interpolator = take_spanned_interpolator("- ${item}".into()).unwrap();
         let segments = match interpolator {
             SpannedStringInterpolator { segments, postfix } if postfix.as_ref() == "" => segments,
             err => unreachable!("{:?}", err),
@@ -125,7 +385,10 @@ mod test {
                 accessor:
                     SpannedAccessor {
                         keys,
-                        span: AccessorParserSpan { start: 2, end: 9 },
+                        span:
+                            AccessorParserSpan {
+                                start: 2, end: 9, ..
+                            },
                     },
             }] if prefix.as_ref() == "- " => keys,
             err => unreachable!("{:?}", err),
@@ -134,7 +397,10 @@ mod test {
         match keys.as_ref() {
             [SpannedAccessorKey {
                 key: AccessorKey::String(key),
-                span: AccessorParserSpan { start: 4, end: 8 },
+                span:
+                    AccessorParserSpan {
+                        start: 4, end: 8, ..
+                    },
             }] if key.as_ref() == "item" => {}
             err => unreachable!("{:?}", err),
         }
@@ -142,7 +408,7 @@ mod test {
 
     #[test]
     fn should_take_string_interpolation_with_pre_and_postfix() {
-        let interpolator = take_spanned_string_interpolator("- ${item} -".into()).unwrap();
+        let interpolator = take_spanned_interpolator("- ${item} -".into()).unwrap();
         let segments = match interpolator {
             SpannedStringInterpolator { segments, postfix } if postfix.as_ref() == " -" => segments,
             err => unreachable!("{:?}", err),
@@ -154,7 +420,10 @@ mod test {
                 accessor:
                     SpannedAccessor {
                         keys,
-                        span: AccessorParserSpan { start: 2, end: 9 },
+                        span:
+                            AccessorParserSpan {
+                                start: 2, end: 9, ..
+                            },
                     },
             }] if prefix.as_ref() == "- " => keys,
             err => unreachable!("{:?}", err),
@@ -163,7 +432,10 @@ mod test {
         match keys.as_ref() {
             [SpannedAccessorKey {
                 key: AccessorKey::String(key),
-                span: AccessorParserSpan { start: 4, end: 8 },
+                span:
+                    AccessorParserSpan {
+                        start: 4, end: 8, ..
+                    },
             }] if key.as_ref() == "item" => {}
             err => unreachable!("{:?}", err),
         }
@@ -172,7 +444,7 @@ mod test {
     #[test]
     fn should_take_string_interpolation_with_multiple_accessor() {
         let interpolator =
-            take_spanned_string_interpolator("${event.created_ms} - ${item}".into()).unwrap();
+            take_spanned_interpolator("${event.created_ms} - ${item}".into()).unwrap();
         let segments = match interpolator {
             SpannedStringInterpolator { segments, postfix } if postfix.as_ref() == "" => segments,
             err => unreachable!("{:?}", err),
@@ -184,14 +456,20 @@ mod test {
                 accessor:
                     SpannedAccessor {
                         keys: keys1,
-                        span: AccessorParserSpan { start: 0, end: 19 },
+                        span:
+                            AccessorParserSpan {
+                                start: 0, end: 19, ..
+                            },
                     },
             }, SpannedInterpolatorSegment {
                 prefix: prefix2,
                 accessor:
                     SpannedAccessor {
                         keys: keys2,
-                        span: AccessorParserSpan { start: 22, end: 29 },
+                        span:
+                            AccessorParserSpan {
+                                start: 22, end: 29, ..
+                            },
                     },
             }] if prefix1.as_ref() == "" && prefix2.as_ref() == " - " => (keys1, keys2),
             err => unreachable!("{:?}", err),
@@ -200,10 +478,16 @@ mod test {
         match keys1.as_ref() {
             [SpannedAccessorKey {
                 key: AccessorKey::String(key1),
-                span: AccessorParserSpan { start: 2, end: 7 },
+                span:
+                    AccessorParserSpan {
+                        start: 2, end: 7, ..
+                    },
             }, SpannedAccessorKey {
                 key: AccessorKey::String(key2),
-                span: AccessorParserSpan { start: 7, end: 18 },
+                span:
+                    AccessorParserSpan {
+                        start: 7, end: 18, ..
+                    },
             }] if key1.as_ref() == "event" && key2.as_ref() == "created_ms" => {}
             err => unreachable!("{:?}", err),
         }
@@ -211,9 +495,258 @@ mod test {
         match keys2.as_ref() {
             [SpannedAccessorKey {
                 key: AccessorKey::String(key1),
-                span: AccessorParserSpan { start: 24, end: 28 },
+                span:
+                    AccessorParserSpan {
+                        start: 24, end: 28, ..
+                    },
             }] if key1.as_ref() == "item" => {}
             err => unreachable!("{:?}", err),
         }
     }
+
+    #[test]
+    fn should_parse_string_interpolator() {
+        let interpolator: StringInterpolator = "${event.created_ms} - ${item}".parse().unwrap();
+        assert_eq!("", interpolator.postfix());
+        match interpolator.segments() {
+            [segment1, segment2] => {
+                assert_eq!("", segment1.prefix());
+                assert_eq!(2, segment1.accessor().keys().len());
+                assert_eq!(" - ", segment2.prefix());
+                assert_eq!(1, segment2.accessor().keys().len());
+            }
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_parse_string_interpolator_on_unclosed_accessor() {
+        let err = SpannedStringInterpolator::parse("- ${item -").unwrap_err();
+        match err {
+            AccessorParserError {
+                kind: AccessorParserErrorKind::MissingClosingBracket,
+                span:
+                    AccessorParserSpan {
+                        start: 2, end: 4, ..
+                    },
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_render_interpolator() {
+        let ctx = test_context();
+
+        let interpolator: StringInterpolator =
+            "created: ${event.created_ms} - tag: ${event.tags[0]}!"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            "created: 1234 - tag: first!",
+            interpolator.render(&ctx).unwrap()
+        );
+
+        let interpolator: StringInterpolator = "no accessor".parse().unwrap();
+        assert_eq!("no accessor", interpolator.render(&ctx).unwrap());
+    }
+
+    #[test]
+    fn should_render_multi_valued_accessor() {
+        let ctx = hashmap! {
+            "tags".to_owned() => vec!["a".to_owned(), "b".to_owned(), "c".to_owned()],
+        };
+
+        let interpolator: StringInterpolator = "[${tags[:2]}] [${tags[::2]}] [${tags[5:]}]"
+            .parse()
+            .unwrap();
+        assert_eq!("[a, b] [a, c] []", interpolator.render(&ctx).unwrap());
+
+        let options = RenderOptions::default().joiner(" | ");
+        assert_eq!(
+            "[a | b] [a | c] []",
+            interpolator.render_with(&ctx, &options).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_render_scalars() {
+        assert_eq!("", Scalar::Null.to_string());
+        assert_eq!("true", Scalar::Bool(true).to_string());
+        assert_eq!("-42", Scalar::Integer(-42).to_string());
+        assert_eq!("1.5", Scalar::Float(1.5).to_string());
+        assert_eq!("3", Scalar::Float(3.0).to_string());
+        assert_eq!("text", Scalar::String("text".into()).to_string());
+
+        let interpolator: StringInterpolator = "${ratio}".parse().unwrap();
+        let ctx = hashmap! { "ratio".to_owned() => 0.1f32 };
+        assert_eq!("0.1", interpolator.render(&ctx).unwrap());
+    }
+
+    #[test]
+    fn should_fail_to_render_missing_key() {
+        let ctx = test_context();
+
+        let interpolator: StringInterpolator = "${event.tags[1]}".parse().unwrap();
+        match interpolator.render(&ctx).unwrap_err() {
+            RenderError {
+                kind:
+                    RenderErrorKind::Eval(EvalError {
+                        kind: EvalErrorKind::IndexOutOfBounds { len: 1 },
+                        position: 2,
+                        ..
+                    }),
+                ..
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_render_non_scalar() {
+        let ctx = test_context();
+
+        let interpolator: StringInterpolator = "${event.tags}".parse().unwrap();
+        match interpolator.render(&ctx).unwrap_err() {
+            RenderError {
+                kind: RenderErrorKind::NotStringRepresentable,
+                span: None,
+                ..
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        let interpolator = SpannedStringInterpolator::parse("tags: ${event.tags}").unwrap();
+        let err = interpolator.render(&ctx).unwrap_err();
+        assert_eq!(&RenderErrorKind::NotStringRepresentable, err.kind());
+        match err.span() {
+            Some(AccessorParserSpan {
+                start: 6, end: 19, ..
+            }) => {}
+            span => unreachable!("{:?}", span),
+        }
+    }
+
+    #[test]
+    fn should_take_multi_line_string_interpolation_with_positions() {
+        let interpolator =
+            SpannedStringInterpolator::parse("Hello ${name},\n\nyour order ${order.id}\n").unwrap();
+        assert_eq!("\n", interpolator.postfix());
+
+        match interpolator.segments() {
+            [_, segment] => match segment.accessor() {
+                SpannedAccessor {
+                    span:
+                        AccessorParserSpan {
+                            start: 27,
+                            end: 38,
+                            start_position:
+                                SourcePosition {
+                                    line: 3,
+                                    column: 12,
+                                },
+                            end_position:
+                                SourcePosition {
+                                    line: 3,
+                                    column: 23,
+                                },
+                        },
+                    keys,
+                } => match keys.as_ref() {
+                    [_, SpannedAccessorKey {
+                        key: AccessorKey::String(key),
+                        span:
+                            AccessorParserSpan {
+                                start: 34,
+                                end: 37,
+                                start_position:
+                                    SourcePosition {
+                                        line: 3,
+                                        column: 19,
+                                    },
+                                end_position:
+                                    SourcePosition {
+                                        line: 3,
+                                        column: 22,
+                                    },
+                            },
+                    }] if key.as_ref() == "id" => {}
+                    err => unreachable!("{:?}", err),
+                },
+                err => unreachable!("{:?}", err),
+            },
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_count_columns_in_characters_and_offsets_in_bytes() {
+        let interpolator = SpannedStringInterpolator::parse("äöü\n€ ${item}").unwrap();
+        match interpolator.segments() {
+            [SpannedInterpolatorSegment {
+                accessor:
+                    SpannedAccessor {
+                        span:
+                            AccessorParserSpan {
+                                start: 11,
+                                end: 18,
+                                start_position: SourcePosition { line: 2, column: 3 },
+                                end_position:
+                                    SourcePosition {
+                                        line: 2,
+                                        column: 10,
+                                    },
+                            },
+                        ..
+                    },
+                ..
+            }] => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_fail_to_parse_multi_line_string_interpolator_with_positions() {
+        let err = SpannedStringInterpolator::parse("line 1 ${a}\nline 2 ${b[x]}").unwrap_err();
+        match err {
+            AccessorParserError {
+                kind: AccessorParserErrorKind::NotANumber,
+                span:
+                    AccessorParserSpan {
+                        start: 23,
+                        end: 24,
+                        start_position:
+                            SourcePosition {
+                                line: 2,
+                                column: 12,
+                            },
+                        end_position:
+                            SourcePosition {
+                                line: 2,
+                                column: 13,
+                            },
+                    },
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        let err = SpannedStringInterpolator::parse("line 1\n\n  ${a.b\\q}").unwrap_err();
+        match err {
+            AccessorParserError {
+                kind: AccessorParserErrorKind::InvalidEscapeCharacter('q'),
+                span:
+                    AccessorParserSpan {
+                        start: 15,
+                        end: 17,
+                        start_position: SourcePosition { line: 3, column: 8 },
+                        end_position:
+                            SourcePosition {
+                                line: 3,
+                                column: 10,
+                            },
+                    },
+            } => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
 }
@@ -1,131 +1,509 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    hash::BuildHasher,
+};
 
 use crate::{
-    error::{AccessorValidationError, AccessorValidationErrorKind},
+    error::{AccessorValidationError, AccessorValidationErrorKind, ValidationReport},
     string_interpolator::SpannedStringInterpolator,
-    AccessorKey, AccessorParserSpan, SpannedAccessor, SpannedAccessorKey,
+    Accessor, AccessorKey, AccessorParserSpan, SpannedAccessor, SpannedAccessorKey,
+};
+
+pub use crate::{
+    builder::PathNodeBuilder,
+    paths::{Paths, SchemaPath},
 };
+#[cfg(feature = "derive")]
+pub use accessor_rs_derive::AccessorSchema;
 
 // ToDo: Wrap node into a function and revert the dependencies for better erroros?
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum PathNode {
-    Node { children: HashMap<String, PathNode> },
+    Node {
+        children: HashMap<String, PathNode>,
+    },
+    /// A list, where every item has the structure of `item`.
+    List {
+        item: Box<PathNode>,
+        #[cfg_attr(feature = "serde", serde(default))]
+        max_len: Option<usize>,
+    },
+    /// A map with arbitrary keys, where every value has the structure of `value`.
+    Map {
+        value: Box<PathNode>,
+    },
     Root,
     ObjectRoot,
-    KnownField,
+    KnownField(FieldType),
+    /// A node, that is still valid but should no longer be used.
+    Deprecated {
+        node: Box<PathNode>,
+        #[cfg_attr(feature = "serde", serde(default))]
+        replacement: Option<String>,
+    },
+}
+
+/// The type of the value stored in a [`PathNode::KnownField`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum FieldType {
+    String,
+    Integer,
+    Float,
+    Bool,
+    Timestamp,
+    /// Any value, that can be represented as a string.
+    Any,
+}
+
+impl FieldType {
+    /// Whether a field of this type can be used where `expected` is required.
+    pub fn satisfies(self, expected: FieldType) -> bool {
+        self == expected || self == FieldType::Any || expected == FieldType::Any
+    }
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FieldType::String => "string",
+            FieldType::Integer => "integer",
+            FieldType::Float => "float",
+            FieldType::Bool => "bool",
+            FieldType::Timestamp => "timestamp",
+            FieldType::Any => "any",
+        };
+        f.write_str(name)
+    }
 }
 
 impl PathNode {
-    pub fn validate_accessor(
+    /// Validates the accessor, see [`ValidationReport::is_ok`].
+    pub fn validate_accessor(&self, accessor: &SpannedAccessor) -> ValidationReport {
+        self.validate_typed_accessor(accessor, None)
+    }
+
+    /// Validates the accessor and checks, that it points to a field of the `expected` type.
+    pub fn validate_typed_accessor(
         &self,
         accessor: &SpannedAccessor,
-    ) -> Result<(), AccessorValidationError> {
-        self.validate(accessor, false)
+        expected: Option<FieldType>,
+    ) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        self.validate(accessor, false, expected, &mut report);
+        report
     }
 
+    /// Validates all accessors of the interpolator, see [`ValidationReport::is_ok`].
     pub fn validate_interpolator(
         &self,
         interpolator: &SpannedStringInterpolator,
-    ) -> Result<(), Vec<AccessorValidationError>> {
-        let mut errors = vec![];
+    ) -> ValidationReport {
+        self.validate_typed_interpolator(interpolator, &[])
+    }
 
-        for segment in &interpolator.segments {
-            let Err(err) = self.validate(&segment.accessor, true) else {
-                continue;
-            };
+    /// Validates the interpolator, where `expected` holds the expected type of each segment in
+    /// order. Segments without an entry accept any type.
+    pub fn validate_typed_interpolator(
+        &self,
+        interpolator: &SpannedStringInterpolator,
+        expected: &[Option<FieldType>],
+    ) -> ValidationReport {
+        let mut report = ValidationReport::default();
 
-            errors.push(err)
+        for (idx, segment) in interpolator.segments.iter().enumerate() {
+            let expected = expected.get(idx).copied().flatten();
+            self.validate(&segment.accessor, true, expected, &mut report);
         }
 
-        if errors.is_empty() {
-            return Ok(());
+        report
+    }
+
+    /// The type of the field the accessor points to.
+    ///
+    /// Paths below a [`PathNode::Root`] or [`PathNode::ObjectRoot`] are [`FieldType::Any`], paths
+    /// that don't end in a field return `None`. Below a `.*` on a [`PathNode::Node`], all
+    /// children have to agree on the type.
+    pub fn field_type(&self, accessor: &Accessor) -> Option<FieldType> {
+        self.field_type_of_keys(accessor.keys())
+    }
+
+    fn field_type_of_keys(&self, keys: &[AccessorKey]) -> Option<FieldType> {
+        let (node, rest) = self.walk(keys)?;
+        match (node.without_deprecation(), rest) {
+            (PathNode::KnownField(field_type), []) => Some(*field_type),
+            (PathNode::Root, _) | (PathNode::ObjectRoot, [_, ..]) => Some(FieldType::Any),
+            (PathNode::Node { children }, [AccessorKey::KeyWildcard, rest @ ..]) => {
+                let mut field_types = children
+                    .values()
+                    .map(|child| child.field_type_of_keys(rest));
+                let field_type = field_types.next()??;
+                field_types
+                    .all(|other| other == Some(field_type))
+                    .then_some(field_type)
+            }
+            _ => None,
+        }
+    }
+
+    /// The node the `keys` lead to. Keys below a [`PathNode::Root`] or [`PathNode::ObjectRoot`]
+    /// lead to that node. A `.*` on a [`PathNode::Node`] leads to no single node.
+    pub fn node_at(&self, keys: &[AccessorKey]) -> Option<&PathNode> {
+        match self.walk(keys)? {
+            (node, []) | (node @ (PathNode::Root | PathNode::ObjectRoot), _) => Some(node),
+            _ => None,
+        }
+    }
+
+    /// Follows the `keys` as far as a single node is known, returning that node and the keys left
+    /// at a [`PathNode::Root`], [`PathNode::ObjectRoot`] or a `.*` on a [`PathNode::Node`].
+    fn walk<'k>(&self, keys: &'k [AccessorKey]) -> Option<(&PathNode, &'k [AccessorKey])> {
+        let mut node = self;
+        for (idx, key) in keys.iter().enumerate() {
+            node = match (node.without_deprecation(), key) {
+                (node @ (PathNode::Root | PathNode::ObjectRoot), _)
+                | (node @ PathNode::Node { .. }, AccessorKey::KeyWildcard) => {
+                    return Some((node, &keys[idx..]))
+                }
+                (PathNode::Node { children }, AccessorKey::String(key)) => {
+                    children.get(key.as_ref())?
+                }
+                (PathNode::Map { value }, AccessorKey::String(_)) => value,
+                (PathNode::List { item, max_len }, AccessorKey::Numeric(index)) => match max_len {
+                    Some(max_len) if index >= max_len => return None,
+                    _ => item,
+                },
+                (PathNode::List { item, max_len }, AccessorKey::NegativeIndex(index)) => {
+                    match max_len {
+                        Some(max_len) if index > max_len => return None,
+                        _ => item,
+                    }
+                }
+                (
+                    PathNode::List { item, .. },
+                    AccessorKey::Slice(_) | AccessorKey::IndexWildcard,
+                ) => item,
+                (PathNode::Map { value }, AccessorKey::KeyWildcard) => value,
+                _ => return None,
+            };
         }
+        Some((node, &[]))
+    }
+
+    /// Deserializes a tree from any serde format, where errors carry the path to the bad entry.
+    ///
+    /// Nodes are written in snake case, e.g. in JSON:
+    ///
+    /// ```json
+    /// { "node": { "children": {
+    ///     "created_ms": { "known_field": "integer" },
+    ///     "tags": { "list": { "item": "root", "max_len": 8 } },
+    ///     "metadata": "object_root"
+    /// } } }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn deserialize_with_path<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<PathNode, serde_path_to_error::Error<D::Error>> {
+        serde_path_to_error::deserialize(deserializer)
+    }
 
-        Err(errors)
+    /// The node itself, or the node wrapped by [`PathNode::Deprecated`].
+    pub(crate) fn without_deprecation(&self) -> &PathNode {
+        match self {
+            PathNode::Deprecated { node, .. } => node.without_deprecation(),
+            node => node,
+        }
     }
 
     fn validate(
         &self,
         accessor: &SpannedAccessor,
         is_interpolator: bool,
-    ) -> Result<(), AccessorValidationError> {
+        expected: Option<FieldType>,
+        report: &mut ValidationReport,
+    ) {
         let SpannedAccessor { keys, span } = accessor;
-        path_contains(self, span, keys, is_interpolator)
+        let mut validator = Validator {
+            accessor_span: span,
+            is_interpolator,
+            expected,
+            report,
+        };
+
+        validator.path_contains(self, span, keys);
     }
 }
 
-fn path_contains(
-    node: &PathNode,
-    accessor_span: &AccessorParserSpan,
-    remaining_keys: &[SpannedAccessorKey],
-    is_interpolator: bool,
-) -> Result<(), AccessorValidationError> {
-    match node {
-        PathNode::Root => Ok(()),
-        PathNode::ObjectRoot if remaining_keys.is_empty() && is_interpolator => {
-            Err(AccessorValidationError {
-                kind: AccessorValidationErrorKind::NotStringRepresentable,
-                span: *accessor_span,
-            })
+/// Types, that can describe their own structure as a [`PathNode`] tree.
+///
+/// Can be derived with `#[derive(AccessorSchema)]`, when the `derive` feature is enabled.
+pub trait HasPathNode {
+    fn path_node() -> PathNode;
+}
+
+impl<T: HasPathNode> HasPathNode for Option<T> {
+    fn path_node() -> PathNode {
+        T::path_node()
+    }
+}
+
+impl<T: HasPathNode + ?Sized> HasPathNode for Box<T> {
+    fn path_node() -> PathNode {
+        T::path_node()
+    }
+}
+
+impl<T: HasPathNode + ?Sized> HasPathNode for &T {
+    fn path_node() -> PathNode {
+        T::path_node()
+    }
+}
+
+impl<T: HasPathNode, S: BuildHasher> HasPathNode for HashMap<String, T, S> {
+    fn path_node() -> PathNode {
+        PathNode::Map {
+            value: Box::new(T::path_node()),
         }
-        PathNode::ObjectRoot => match remaining_keys {
-            []
-            | [SpannedAccessorKey {
-                key: AccessorKey::String(_),
-                ..
-            }, ..] => Ok(()),
-            [SpannedAccessorKey { span, .. }, ..] => Err(AccessorValidationError {
-                kind: AccessorValidationErrorKind::NumericIndexInMap,
-                span: *span,
-            }),
-        },
-        PathNode::KnownField => match remaining_keys {
-            [] => Ok(()),
-            [SpannedAccessorKey { span, .. }, ..] => Err(AccessorValidationError {
-                kind: AccessorValidationErrorKind::NotIndexable,
-                span: *span,
-            }),
-        },
-        PathNode::Node { children } => match remaining_keys {
-            [] if !is_interpolator => Ok(()),
-            [] => Err(AccessorValidationError {
-                kind: AccessorValidationErrorKind::NotStringRepresentable,
-                span: *accessor_span,
-            }),
-            [SpannedAccessorKey {
-                key: AccessorKey::Numeric(_),
-                span,
-            }, ..] => Err(AccessorValidationError {
-                kind: AccessorValidationErrorKind::NumericIndexInMap,
-                span: *span,
-            }),
-            [SpannedAccessorKey {
-                key: AccessorKey::String(key),
-                span,
-            }, remaining_keys @ ..] => match children.get(key.as_ref()) {
-                Some(node) => path_contains(node, accessor_span, remaining_keys, is_interpolator),
-                None => {
-                    let mut keys: Vec<_> = children
-                        .keys()
-                        .cloned()
-                        .map(|s| (edit_distance(&s, key.as_ref()), s))
-                        .collect();
-                    keys.sort();
-                    let keys = keys.into_iter().map(|(_edit_distance, key)| key).collect();
-
-                    Err(AccessorValidationError {
-                        kind: AccessorValidationErrorKind::UnknownKey {
-                            possible_keys: keys,
-                        },
-                        span: *span,
-                    })
+    }
+}
+
+impl<T: HasPathNode> HasPathNode for BTreeMap<String, T> {
+    fn path_node() -> PathNode {
+        PathNode::Map {
+            value: Box::new(T::path_node()),
+        }
+    }
+}
+
+impl<T: HasPathNode> HasPathNode for Vec<T> {
+    fn path_node() -> PathNode {
+        PathNode::List {
+            item: Box::new(T::path_node()),
+            max_len: None,
+        }
+    }
+}
+
+impl<T: HasPathNode> HasPathNode for [T] {
+    fn path_node() -> PathNode {
+        PathNode::List {
+            item: Box::new(T::path_node()),
+            max_len: None,
+        }
+    }
+}
+
+impl<T: HasPathNode, const N: usize> HasPathNode for [T; N] {
+    fn path_node() -> PathNode {
+        PathNode::List {
+            item: Box::new(T::path_node()),
+            max_len: Some(N),
+        }
+    }
+}
+
+macro_rules! impl_known_field {
+    ($field_type:ident: $($ty:ty),* $(,)?) => {
+        $(impl HasPathNode for $ty {
+            fn path_node() -> PathNode {
+                PathNode::KnownField(FieldType::$field_type)
+            }
+        })*
+    };
+}
+
+impl_known_field!(String: String, str, char);
+impl_known_field!(Bool: bool);
+impl_known_field!(Integer: u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_known_field!(Float: f32, f64);
+
+struct Validator<'a> {
+    accessor_span: &'a AccessorParserSpan,
+    is_interpolator: bool,
+    expected: Option<FieldType>,
+    report: &'a mut ValidationReport,
+}
+
+impl Validator<'_> {
+    /// Walks the `node` reached by the key at `span` with the `remaining_keys` and adds every
+    /// problem to the report. The walk only stops at keys, that no schema exists for.
+    fn path_contains(
+        &mut self,
+        node: &PathNode,
+        span: &AccessorParserSpan,
+        remaining_keys: &[SpannedAccessorKey],
+    ) {
+        match node {
+            PathNode::Root => {}
+            PathNode::Deprecated { node, replacement } => {
+                let kind = AccessorValidationErrorKind::DeprecatedField {
+                    replacement: replacement.clone(),
+                };
+                self.error(kind, span);
+                self.path_contains(node, span, remaining_keys)
+            }
+            PathNode::ObjectRoot if remaining_keys.is_empty() => self.check_not_rendered(),
+            PathNode::ObjectRoot => match remaining_keys {
+                []
+                | [SpannedAccessorKey {
+                    key: AccessorKey::String(_) | AccessorKey::KeyWildcard,
+                    ..
+                }, ..] => {}
+                [SpannedAccessorKey { span, .. }, ..] => {
+                    self.error(AccessorValidationErrorKind::NumericIndexInMap, span)
+                }
+            },
+            PathNode::KnownField(field_type) => match remaining_keys {
+                [] => self.check_field_type(*field_type),
+                [SpannedAccessorKey { span, .. }, ..] => {
+                    self.error(AccessorValidationErrorKind::NotIndexable, span)
                 }
             },
-        },
+            PathNode::List { item, max_len } => match remaining_keys {
+                [] => self.check_not_rendered(),
+                [SpannedAccessorKey {
+                    key: AccessorKey::String(_) | AccessorKey::KeyWildcard,
+                    span,
+                }, ..] => self.error(AccessorValidationErrorKind::StringKeyInList, span),
+                [SpannedAccessorKey {
+                    key: AccessorKey::Numeric(index),
+                    span,
+                }, remaining_keys @ ..] => match max_len {
+                    // Any item has the same schema, so the rest of the path is still checked.
+                    Some(max_len) if index >= max_len => {
+                        let kind =
+                            AccessorValidationErrorKind::IndexOutOfBounds { max_len: *max_len };
+                        self.error(kind, span);
+                        self.path_contains(item, span, remaining_keys)
+                    }
+                    _ => self.path_contains(item, span, remaining_keys),
+                },
+                [SpannedAccessorKey {
+                    key: AccessorKey::NegativeIndex(index),
+                    span,
+                }, remaining_keys @ ..] => match max_len {
+                    Some(max_len) if index > max_len => {
+                        let kind =
+                            AccessorValidationErrorKind::IndexOutOfBounds { max_len: *max_len };
+                        self.error(kind, span);
+                        self.path_contains(item, span, remaining_keys)
+                    }
+                    _ => self.path_contains(item, span, remaining_keys),
+                },
+                [SpannedAccessorKey {
+                    key: AccessorKey::Slice(_) | AccessorKey::IndexWildcard,
+                    span,
+                }, remaining_keys @ ..] => self.path_contains(item, span, remaining_keys),
+            },
+            PathNode::Map { value } => match remaining_keys {
+                [] => self.check_not_rendered(),
+                [SpannedAccessorKey {
+                    key:
+                        AccessorKey::Numeric(_)
+                        | AccessorKey::NegativeIndex(_)
+                        | AccessorKey::Slice(_)
+                        | AccessorKey::IndexWildcard,
+                    span,
+                }, ..] => self.error(AccessorValidationErrorKind::NumericIndexInMap, span),
+                [SpannedAccessorKey {
+                    key: AccessorKey::String(_) | AccessorKey::KeyWildcard,
+                    span,
+                }, remaining_keys @ ..] => self.path_contains(value, span, remaining_keys),
+            },
+            PathNode::Node { children } => match remaining_keys {
+                [] => self.check_not_rendered(),
+                // Every child has to allow the rest of the path.
+                [SpannedAccessorKey {
+                    key: AccessorKey::KeyWildcard,
+                    span,
+                }, remaining_keys @ ..] => {
+                    let mut children: Vec<_> = children.iter().collect();
+                    children.sort_by_key(|(key, _)| *key);
+                    for (_, node) in children {
+                        self.path_contains(node, span, remaining_keys);
+                    }
+                }
+                [SpannedAccessorKey {
+                    key:
+                        AccessorKey::Numeric(_)
+                        | AccessorKey::NegativeIndex(_)
+                        | AccessorKey::Slice(_)
+                        | AccessorKey::IndexWildcard,
+                    span,
+                }, ..] => self.error(AccessorValidationErrorKind::NumericIndexInMap, span),
+                [SpannedAccessorKey {
+                    key: AccessorKey::String(key),
+                    span,
+                }, remaining_keys @ ..] => match children.get(key.as_ref()) {
+                    Some(node) => self.path_contains(node, span, remaining_keys),
+                    None => {
+                        let mut keys: Vec<_> = children
+                            .keys()
+                            .cloned()
+                            .map(|s| (edit_distance(&s, key.as_ref()), s))
+                            .collect();
+                        keys.sort();
+                        let keys = keys.into_iter().map(|(_edit_distance, key)| key).collect();
+
+                        let kind = AccessorValidationErrorKind::UnknownKey {
+                            possible_keys: keys,
+                        };
+                        self.error(kind, span)
+                    }
+                },
+            },
+        }
+    }
+
+    fn error(&mut self, kind: AccessorValidationErrorKind, span: &AccessorParserSpan) {
+        self.report
+            .push(AccessorValidationError { kind, span: *span });
+    }
+
+    /// Containers can be accessed as a whole, but not rendered into a string.
+    fn check_not_rendered(&mut self) {
+        if self.is_interpolator {
+            self.error(
+                AccessorValidationErrorKind::NotStringRepresentable,
+                self.accessor_span,
+            );
+        }
+    }
+
+    fn check_field_type(&mut self, field_type: FieldType) {
+        match self.expected {
+            Some(expected) if !field_type.satisfies(expected) => {
+                let kind = AccessorValidationErrorKind::TypeMismatch {
+                    expected,
+                    found: field_type,
+                };
+                self.error(kind, self.accessor_span)
+            }
+            // Without an expected type, the field is rendered with its default format.
+            None if self.is_interpolator
+                && matches!(field_type, FieldType::Timestamp | FieldType::Float) =>
+            {
+                let kind = AccessorValidationErrorKind::SuspiciousInString { field_type };
+                self.error(kind, self.accessor_span)
+            }
+            _ => {}
+        }
     }
 }
 
 // see wikipedia: https://en.wikipedia.org/wiki/Levenshtein_distance
-fn edit_distance(s1: &str, s2: &str) -> u32 {
+pub(crate) fn edit_distance(s1: &str, s2: &str) -> u32 {
     // store character and distance into a struct to avoid char boundary problems while indexing
     struct DistanceMapEntry {
         distance: u32,
@@ -187,24 +565,50 @@ fn edit_distance(s1: &str, s2: &str) -> u32 {
 mod test {
     use maplit::hashmap;
 
-    use super::{edit_distance, PathNode};
+    use std::collections::HashMap;
+
+    use super::{edit_distance, FieldType, HasPathNode, PathNode};
     use crate::{
-        parser::take_spanned_accessor, string_interpolator::take_spanned_string_interpolator,
+        error::{AccessorValidationError, AccessorValidationErrorKind, Severity},
+        parser::take_spanned_accessor,
+        string_interpolator::{take_spanned_interpolator, SpannedStringInterpolator},
+        Accessor, AccessorParserSpan, SourcePosition,
     };
 
     fn test_path_tree() -> PathNode {
         PathNode::Node {
             children: hashmap! {
                 "event".to_owned() => PathNode::Node { children: hashmap! {
-                    "created_ms".to_owned() => PathNode::KnownField,
+                    "created_ms".to_owned() => PathNode::KnownField(FieldType::Integer),
+                    "metadata_ts".to_owned() => PathNode::KnownField(FieldType::Float),
                     "metadata".to_owned() => PathNode::ObjectRoot,
                     "payload".to_owned() => PathNode::ObjectRoot,
+                    "tags".to_owned() => PathNode::List {
+                        item: Box::new(PathNode::Node { children: hashmap! {
+                            "name".to_owned() => PathNode::KnownField(FieldType::String),
+                        }}),
+                        max_len: None,
+                    },
+                    "position".to_owned() => PathNode::List {
+                        item: Box::new(PathNode::KnownField(FieldType::Float)),
+                        max_len: Some(2),
+                    },
                 }},
                 "item".to_owned() => PathNode::Root,
+                "sources".to_owned() => PathNode::Map {
+                    value: Box::new(PathNode::Node { children: hashmap! {
+                        "host".to_owned() => PathNode::KnownField(FieldType::String),
+                        "port".to_owned() => PathNode::KnownField(FieldType::Integer),
+                    }}),
+                },
+                "created".to_owned() => PathNode::Deprecated {
+                    node: Box::new(PathNode::KnownField(FieldType::Timestamp)),
+                    replacement: Some("event.created_ms".to_owned()),
+                },
                 "_variables".to_owned() => PathNode::Node { children: hashmap! {
                     "target1".to_owned() => PathNode::Root,
                     "target2".to_owned() => PathNode::Root,
-                    "target3".to_owned() => PathNode::KnownField,
+                    "target3".to_owned() => PathNode::KnownField(FieldType::Any),
                 }},
             },
         }
@@ -223,13 +627,13 @@ mod test {
         let valid_mappings = test_path_tree();
 
         let (_, accessor) = take_spanned_accessor("${event.created_ms}".into()).unwrap();
-        valid_mappings.validate_accessor(&accessor).unwrap();
+        assert!(valid_mappings.validate_accessor(&accessor).is_ok());
 
         let (_, accessor) = take_spanned_accessor("${item}".into()).unwrap();
-        valid_mappings.validate_accessor(&accessor).unwrap();
+        assert!(valid_mappings.validate_accessor(&accessor).is_ok());
 
         let (_, accessor) = take_spanned_accessor("${_variables.target1.pippo}".into()).unwrap();
-        valid_mappings.validate_accessor(&accessor).unwrap();
+        assert!(valid_mappings.validate_accessor(&accessor).is_ok());
     }
 
     #[test]
@@ -237,16 +641,561 @@ mod test {
         let valid_mappings = test_path_tree();
 
         let interpolator =
-            take_spanned_string_interpolator("${event.created_ms} - ${item}".into()).unwrap();
-        valid_mappings.validate_interpolator(&interpolator).unwrap();
+            take_spanned_interpolator("${event.created_ms} - ${item}".into()).unwrap();
+        assert!(valid_mappings.validate_interpolator(&interpolator).is_ok());
 
         let interpolator =
-            take_spanned_string_interpolator("${item.pippo} - _variables.target1[1234]".into())
-                .unwrap();
-        valid_mappings.validate_interpolator(&interpolator).unwrap();
+            take_spanned_interpolator("${item.pippo} - _variables.target1[1234]".into()).unwrap();
+        assert!(valid_mappings.validate_interpolator(&interpolator).is_ok());
+
+        let interpolator = take_spanned_interpolator("${_variables.target1.pippo}".into()).unwrap();
+        assert!(valid_mappings.validate_interpolator(&interpolator).is_ok());
+    }
+
+    #[test]
+    fn should_build_path_node_from_types() {
+        assert_eq!(
+            PathNode::KnownField(FieldType::Integer),
+            <Option<Box<u64>>>::path_node()
+        );
+        assert_eq!(
+            PathNode::Map {
+                value: Box::new(PathNode::KnownField(FieldType::Integer))
+            },
+            <HashMap<String, u64>>::path_node()
+        );
+        assert_eq!(
+            PathNode::List {
+                item: Box::new(PathNode::KnownField(FieldType::String)),
+                max_len: None
+            },
+            <Vec<String>>::path_node()
+        );
+        assert_eq!(
+            PathNode::List {
+                item: Box::new(PathNode::KnownField(FieldType::Integer)),
+                max_len: Some(3)
+            },
+            <[u8; 3]>::path_node()
+        );
+    }
+
+    #[test]
+    fn should_validate_list_items() {
+        let valid_mappings = test_path_tree();
+
+        let (_, accessor) = take_spanned_accessor("${event.tags[0].name}".into()).unwrap();
+        assert!(valid_mappings.validate_accessor(&accessor).is_ok());
+
+        let (_, accessor) = take_spanned_accessor("${event.position[1]}".into()).unwrap();
+        assert!(valid_mappings.validate_accessor(&accessor).is_ok());
+
+        let (_, accessor) = take_spanned_accessor("${event.tags[0].nam}".into()).unwrap();
+        match valid_mappings.validate_accessor(&accessor).entries() {
+            [AccessorValidationError {
+                kind: AccessorValidationErrorKind::UnknownKey { possible_keys },
+                ..
+            }] => assert_eq!(&vec!["name".to_owned()], possible_keys),
+            err => unreachable!("{:?}", err),
+        }
+
+        let (_, accessor) = take_spanned_accessor("${event.tags.name}".into()).unwrap();
+        match valid_mappings.validate_accessor(&accessor).entries() {
+            [AccessorValidationError {
+                kind: AccessorValidationErrorKind::StringKeyInList,
+                span:
+                    AccessorParserSpan {
+                        start: 12, end: 17, ..
+                    },
+            }] => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        let (_, accessor) = take_spanned_accessor("${event.position[2]}".into()).unwrap();
+        match valid_mappings.validate_accessor(&accessor).entries() {
+            [AccessorValidationError {
+                kind: AccessorValidationErrorKind::IndexOutOfBounds { max_len: 2 },
+                span:
+                    AccessorParserSpan {
+                        start: 16, end: 19, ..
+                    },
+            }] => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        let (_, accessor) = take_spanned_accessor("${event.position[-2]}".into()).unwrap();
+        assert!(valid_mappings.validate_accessor(&accessor).is_ok());
+        let (_, accessor) = take_spanned_accessor("${event.position[5:]}".into()).unwrap();
+        assert!(valid_mappings.validate_accessor(&accessor).is_ok());
+        let (_, accessor) = take_spanned_accessor("${event.position[-3]}".into()).unwrap();
+        match valid_mappings.validate_accessor(&accessor).entries() {
+            [AccessorValidationError {
+                kind: AccessorValidationErrorKind::IndexOutOfBounds { max_len: 2 },
+                span:
+                    AccessorParserSpan {
+                        start: 16, end: 20, ..
+                    },
+            }] => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        let interpolator = SpannedStringInterpolator::parse("${event.tags}").unwrap();
+        match valid_mappings
+            .validate_interpolator(&interpolator)
+            .entries()
+        {
+            [AccessorValidationError {
+                kind: AccessorValidationErrorKind::NotStringRepresentable,
+                ..
+            }] => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_query_field_types() {
+        let valid_mappings = test_path_tree();
+        let field_type = |accessor: &str| valid_mappings.field_type(&accessor.parse().unwrap());
+
+        assert_eq!(Some(FieldType::Integer), field_type("${event.created_ms}"));
+        assert_eq!(Some(FieldType::String), field_type("${event.tags[3].name}"));
+        assert_eq!(Some(FieldType::Float), field_type("${event.position[1]}"));
+        assert_eq!(
+            Some(FieldType::Any),
+            field_type("${event.metadata.anything}")
+        );
+        assert_eq!(Some(FieldType::String), field_type("${sources.any.host}"));
+        assert_eq!(None, field_type("${event.position[2]}"));
+        assert_eq!(Some(FieldType::Float), field_type("${event.position[-2]}"));
+        assert_eq!(None, field_type("${event.position[-3]}"));
+        assert_eq!(
+            Some(FieldType::String),
+            field_type("${event.tags[:2].name}")
+        );
+        assert_eq!(Some(FieldType::String), field_type("${event.tags[*].name}"));
+        assert_eq!(Some(FieldType::Integer), field_type("${sources.*.port}"));
+        assert_eq!(Some(FieldType::Any), field_type("${_variables.*}"));
+        assert_eq!(None, field_type("${event.*}"));
+        assert_eq!(None, field_type("${event}"));
+        assert_eq!(None, field_type("${event.unknown}"));
+    }
+
+    #[test]
+    fn should_find_nodes() {
+        let valid_mappings = test_path_tree();
+        let node_at = |accessor: &str| {
+            let accessor: Accessor = accessor.parse().unwrap();
+            valid_mappings.node_at(accessor.keys()).cloned()
+        };
+
+        assert_eq!(
+            Some(PathNode::KnownField(FieldType::String)),
+            node_at("${sources.any.host}")
+        );
+        assert_eq!(
+            Some(PathNode::ObjectRoot),
+            node_at("${event.metadata.a[0]}")
+        );
+        assert!(matches!(
+            node_at("${created}"),
+            Some(PathNode::Deprecated { .. })
+        ));
+        assert_eq!(None, node_at("${event.position[2]}"));
+        assert_eq!(None, node_at("${event.position[-3]}"));
+        assert_eq!(
+            Some(PathNode::KnownField(FieldType::String)),
+            node_at("${event.tags[*].name}")
+        );
+        assert_eq!(None, node_at("${event.*}"));
+        assert_eq!(None, node_at("${event.unknown}"));
+        assert_eq!(
+            Some(valid_mappings.clone()),
+            valid_mappings.node_at(&[]).cloned()
+        );
+    }
+
+    #[test]
+    fn should_report_type_mismatches() {
+        let valid_mappings = test_path_tree();
+
+        let (_, accessor) = take_spanned_accessor("${event.metadata_ts}".into()).unwrap();
+        assert!(valid_mappings
+            .validate_typed_accessor(&accessor, Some(FieldType::Float))
+            .is_ok());
+        match valid_mappings
+            .validate_typed_accessor(&accessor, Some(FieldType::Timestamp))
+            .entries()
+        {
+            [AccessorValidationError {
+                kind:
+                    AccessorValidationErrorKind::TypeMismatch {
+                        expected: FieldType::Timestamp,
+                        found: FieldType::Float,
+                    },
+                span:
+                    AccessorParserSpan {
+                        start: 0, end: 20, ..
+                    },
+            }] => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        let interpolator = SpannedStringInterpolator::parse(
+            "${event.created_ms} ${_variables.target3} ${event.tags[0].name}",
+        )
+        .unwrap();
+        let expected = [Some(FieldType::Timestamp), Some(FieldType::Timestamp)];
+        match valid_mappings
+            .validate_typed_interpolator(&interpolator, &expected)
+            .entries()
+        {
+            [AccessorValidationError {
+                kind:
+                    AccessorValidationErrorKind::TypeMismatch {
+                        expected: FieldType::Timestamp,
+                        found: FieldType::Integer,
+                    },
+                ..
+            }] => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_collect_all_problems_in_report() {
+        let valid_mappings = test_path_tree();
 
         let interpolator =
-            take_spanned_string_interpolator("${_variables.target1.pippo}".into()).unwrap();
-        valid_mappings.validate_interpolator(&interpolator).unwrap();
+            SpannedStringInterpolator::parse("${created} ${event.metadata_ts} ${item}").unwrap();
+        let report = valid_mappings.validate_interpolator(&interpolator);
+        assert!(report.is_ok());
+        match report.entries() {
+            [AccessorValidationError {
+                kind: AccessorValidationErrorKind::DeprecatedField { .. },
+                span:
+                    AccessorParserSpan {
+                        start: 2, end: 9, ..
+                    },
+            }, AccessorValidationError {
+                kind:
+                    AccessorValidationErrorKind::SuspiciousInString {
+                        field_type: FieldType::Timestamp,
+                    },
+                span:
+                    AccessorParserSpan {
+                        start: 0, end: 10, ..
+                    },
+            }, AccessorValidationError {
+                kind:
+                    AccessorValidationErrorKind::SuspiciousInString {
+                        field_type: FieldType::Float,
+                    },
+                ..
+            }] => {}
+            err => unreachable!("{:?}", err),
+        }
+        assert_eq!(2, report.warnings().count());
+        assert_eq!(1, report.with_severity(Severity::Info).count());
+
+        let interpolator =
+            SpannedStringInterpolator::parse("${created.value} ${event.create_ms} ${item}")
+                .unwrap();
+        let report = valid_mappings.validate_interpolator(&interpolator);
+        assert!(!report.is_ok());
+        match report.entries() {
+            [AccessorValidationError {
+                kind: AccessorValidationErrorKind::DeprecatedField { .. },
+                ..
+            }, AccessorValidationError {
+                kind: AccessorValidationErrorKind::NotIndexable,
+                ..
+            }, AccessorValidationError {
+                kind: AccessorValidationErrorKind::UnknownKey { .. },
+                ..
+            }] => {}
+            err => unreachable!("{:?}", err),
+        }
+        assert_eq!(2, report.errors().count());
+    }
+
+    #[test]
+    fn should_validate_wildcards() {
+        let valid_mappings = test_path_tree();
+
+        for accessor in [
+            "${event.tags[*].name}",
+            "${sources.*.port}",
+            "${event.metadata.*}",
+            "${_variables.*}",
+        ] {
+            let (_, accessor) = take_spanned_accessor(accessor.into()).unwrap();
+            assert!(valid_mappings.validate_accessor(&accessor).is_ok());
+        }
+
+        // Every child of a node is walked.
+        let interpolator = SpannedStringInterpolator::parse("${event.*}").unwrap();
+        match valid_mappings
+            .validate_interpolator(&interpolator)
+            .entries()
+        {
+            [AccessorValidationError {
+                kind: AccessorValidationErrorKind::NotStringRepresentable,
+                ..
+            }, AccessorValidationError {
+                kind:
+                    AccessorValidationErrorKind::SuspiciousInString {
+                        field_type: FieldType::Float,
+                    },
+                ..
+            }] => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        let (_, accessor) = take_spanned_accessor("${event.tags.*}".into()).unwrap();
+        match valid_mappings.validate_accessor(&accessor).entries() {
+            [AccessorValidationError {
+                kind: AccessorValidationErrorKind::StringKeyInList,
+                span:
+                    AccessorParserSpan {
+                        start: 12, end: 14, ..
+                    },
+            }] => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        let (_, accessor) = take_spanned_accessor("${sources[*]}".into()).unwrap();
+        match valid_mappings.validate_accessor(&accessor).entries() {
+            [AccessorValidationError {
+                kind: AccessorValidationErrorKind::NumericIndexInMap,
+                ..
+            }] => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_keep_walking_after_non_fatal_errors() {
+        let valid_mappings = PathNode::Node {
+            children: hashmap! {
+                "l".to_owned() => PathNode::List {
+                    item: Box::new(PathNode::Node { children: hashmap! {
+                        "foo".to_owned() => PathNode::KnownField(FieldType::String),
+                    }}),
+                    max_len: Some(2),
+                },
+            },
+        };
+
+        let (_, accessor) = take_spanned_accessor("${l[5].bar}".into()).unwrap();
+        match valid_mappings.validate_accessor(&accessor).entries() {
+            [AccessorValidationError {
+                kind: AccessorValidationErrorKind::IndexOutOfBounds { max_len: 2 },
+                span:
+                    AccessorParserSpan {
+                        start: 3, end: 6, ..
+                    },
+            }, AccessorValidationError {
+                kind: AccessorValidationErrorKind::UnknownKey { possible_keys },
+                span:
+                    AccessorParserSpan {
+                        start: 6, end: 10, ..
+                    },
+            }] => assert_eq!(&vec!["foo".to_owned()], possible_keys),
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_report_errors_of_every_wildcard_child() {
+        let valid_mappings = PathNode::Node {
+            children: hashmap! {
+                "event".to_owned() => PathNode::Node { children: hashmap! {
+                    "a".to_owned() => PathNode::Node { children: hashmap! {
+                        "x".to_owned() => PathNode::KnownField(FieldType::String),
+                    }},
+                    "b".to_owned() => PathNode::Node { children: hashmap! {
+                        "y".to_owned() => PathNode::KnownField(FieldType::String),
+                    }},
+                    "c".to_owned() => PathNode::Node { children: hashmap! {
+                        "x".to_owned() => PathNode::KnownField(FieldType::String),
+                    }},
+                }},
+            },
+        };
+
+        // The errors of `a` and `c` are the same, so they are reported once.
+        let (_, accessor) = take_spanned_accessor("${event.*.z}".into()).unwrap();
+        match valid_mappings.validate_accessor(&accessor).entries() {
+            [AccessorValidationError {
+                kind: AccessorValidationErrorKind::UnknownKey { possible_keys: a },
+                ..
+            }, AccessorValidationError {
+                kind: AccessorValidationErrorKind::UnknownKey { possible_keys: b },
+                ..
+            }] => {
+                assert_eq!(&vec!["x".to_owned()], a);
+                assert_eq!(&vec!["y".to_owned()], b);
+            }
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_report_wildcard_warnings_once() {
+        let valid_mappings = PathNode::Node {
+            children: hashmap! {
+                "ratios".to_owned() => PathNode::Node { children: hashmap! {
+                    "a".to_owned() => PathNode::KnownField(FieldType::Float),
+                    "b".to_owned() => PathNode::KnownField(FieldType::Float),
+                }},
+            },
+        };
+
+        let interpolator = SpannedStringInterpolator::parse("${ratios.*}").unwrap();
+        let report = valid_mappings.validate_interpolator(&interpolator);
+        assert!(report.is_ok());
+        match report.entries() {
+            [AccessorValidationError {
+                kind:
+                    AccessorValidationErrorKind::SuspiciousInString {
+                        field_type: FieldType::Float,
+                    },
+                ..
+            }] => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_validate_map_values() {
+        let valid_mappings = test_path_tree();
+
+        let (_, accessor) = take_spanned_accessor("${sources.anything.host}".into()).unwrap();
+        assert!(valid_mappings.validate_accessor(&accessor).is_ok());
+
+        let (_, accessor) = take_spanned_accessor("${sources.anything}".into()).unwrap();
+        assert!(valid_mappings.validate_accessor(&accessor).is_ok());
+
+        let (_, accessor) = take_spanned_accessor("${sources.anything.hots}".into()).unwrap();
+        match valid_mappings.validate_accessor(&accessor).entries() {
+            [AccessorValidationError {
+                kind: AccessorValidationErrorKind::UnknownKey { possible_keys },
+                span:
+                    AccessorParserSpan {
+                        start: 18, end: 23, ..
+                    },
+            }] => assert_eq!(&vec!["host".to_owned(), "port".to_owned()], possible_keys),
+            err => unreachable!("{:?}", err),
+        }
+
+        let (_, accessor) = take_spanned_accessor("${sources[0].host}".into()).unwrap();
+        match valid_mappings.validate_accessor(&accessor).entries() {
+            [AccessorValidationError {
+                kind: AccessorValidationErrorKind::NumericIndexInMap,
+                ..
+            }] => {}
+            err => unreachable!("{:?}", err),
+        }
+
+        let (_, accessor) = take_spanned_accessor("${sources[1:].host}".into()).unwrap();
+        match valid_mappings.validate_accessor(&accessor).entries() {
+            [AccessorValidationError {
+                kind: AccessorValidationErrorKind::NumericIndexInMap,
+                span:
+                    AccessorParserSpan {
+                        start: 9, end: 13, ..
+                    },
+            }] => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[test]
+    fn should_report_validation_errors_on_multi_line_templates() {
+        let valid_mappings = test_path_tree();
+
+        let interpolator = SpannedStringInterpolator::parse(
+            "Created: ${event.created_ms}\nItem: ${item}\nTarget: ${_variables.target4}\n",
+        )
+        .unwrap();
+        let report = valid_mappings.validate_interpolator(&interpolator);
+        assert!(!report.is_ok());
+
+        match report.entries() {
+            [AccessorValidationError {
+                kind: AccessorValidationErrorKind::UnknownKey { .. },
+                span:
+                    AccessorParserSpan {
+                        start: 63,
+                        end: 71,
+                        start_position:
+                            SourcePosition {
+                                line: 3,
+                                column: 21,
+                            },
+                        end_position:
+                            SourcePosition {
+                                line: 3,
+                                column: 29,
+                            },
+                    },
+            }] => {}
+            err => unreachable!("{:?}", err),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_deserialize_path_node_from_json() {
+        let json = r#"{ "node": { "children": {
+            "event": { "node": { "children": {
+                "created_ms": { "known_field": "integer" },
+                "tags": { "list": { "item": { "known_field": "string" }, "max_len": 8 } },
+                "metadata": "object_root"
+            } } },
+            "item": "root"
+        } } }"#;
+
+        let expected = PathNode::Node {
+            children: hashmap! {
+                "event".to_owned() => PathNode::Node { children: hashmap! {
+                    "created_ms".to_owned() => PathNode::KnownField(FieldType::Integer),
+                    "tags".to_owned() => PathNode::List {
+                        item: Box::new(PathNode::KnownField(FieldType::String)),
+                        max_len: Some(8),
+                    },
+                    "metadata".to_owned() => PathNode::ObjectRoot,
+                }},
+                "item".to_owned() => PathNode::Root,
+            },
+        };
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        assert_eq!(
+            expected,
+            PathNode::deserialize_with_path(&mut deserializer).unwrap()
+        );
+
+        let serialized = serde_json::to_string(&test_path_tree()).unwrap();
+        let mut deserializer = serde_json::Deserializer::from_str(&serialized);
+        assert_eq!(
+            test_path_tree(),
+            PathNode::deserialize_with_path(&mut deserializer).unwrap()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_report_path_of_invalid_entry() {
+        let toml = r#"
+            [node.children.event.node.children]
+            created_ms = { known_field = "integer" }
+            tags = { list = { item = { known_field = "text" } } }
+        "#;
+
+        let err = PathNode::deserialize_with_path(toml::Deserializer::new(toml)).unwrap_err();
+        assert_eq!(
+            "node.children.event.node.children.tags.list.item.known_field",
+            err.path().to_string()
+        );
     }
 }